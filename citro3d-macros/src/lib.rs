@@ -1,15 +1,20 @@
 //! Procedural macro helpers for `citro3d`.
 
 // we're already nightly-only so might as well use unstable proc macro APIs.
+#![feature(proc_macro_diagnostic)]
 #![feature(proc_macro_span)]
 
+use std::collections::hash_map::DefaultHasher;
 use std::error::Error;
 use std::fs::DirBuilder;
-use std::path::PathBuf;
-use std::{env, process};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::{env, io, process};
+
+mod vertex;
 
 use litrs::StringLit;
-use proc_macro::TokenStream;
+use proc_macro::{Delimiter, TokenStream, TokenTree};
 use quote::quote;
 
 /// Compiles the given PICA200 shader using [`picasso`](https://github.com/devkitPro/picasso)
@@ -18,12 +23,38 @@ use quote::quote;
 /// This is similar to the standard library's [`include_bytes!`](std::include_bytes) macro, for which
 /// file paths are relative to the source file where the macro is invoked.
 ///
-/// The compiled shader binary will be saved in the caller's `$OUT_DIR`.
+/// The compiled shader binary will be saved in the caller's `$OUT_DIR`, and is
+/// reused by later expansions as long as the shader source, the files it
+/// `.include`s, the include directories and the options are all unchanged, so
+/// `picasso` only runs when needed. Changing the shader or any file it includes
+/// causes the calling crate to be rebuilt and the shader to be recompiled.
+///
+/// Any warnings printed by `picasso` are reported as compiler warnings. They
+/// are saved alongside the compiled shader, so they are reported again when
+/// the cached shader is reused.
+///
+/// # Include directories
+///
+/// Additional directories to search for files included by the shader may be
+/// given with `includes = [...]`. Like the shader path, these are relative to
+/// the source file where the macro is invoked. As with `picasso`, included files
+/// are looked up next to the file including them first, then in these directories.
+///
+/// # Options
+///
+/// * `no_nop = true` passes `--no-nop` to `picasso`, which disables the
+///   automatic insertion of padding `nop` instructions. Defaults to `false`.
+///
+/// `picasso` has no debug or optimization levels: the output always contains
+/// the uniform names used to look up uniforms at runtime (e.g. with
+/// `citro3d::shader::Program::get_uniform`), so they can't be stripped.
+/// Unknown options are rejected.
 ///
 /// # Errors
 ///
-/// This macro will fail to compile if the input is not a single string literal.
-/// In other words, inputs like `concat!("foo", "/bar")` are not supported.
+/// This macro will fail to compile if the shader path or include directories
+/// are not string literals. In other words, inputs like `concat!("foo", "/bar")`
+/// are not supported.
 ///
 /// # Example
 ///
@@ -31,6 +62,8 @@ use quote::quote;
 /// use citro3d_macros::include_shader;
 ///
 /// static SHADER_BYTES: &[u8] = include_shader!("../tests/integration.pica");
+/// static WITH_INCLUDES: &[u8] = include_shader!("../tests/integration.pica", includes = ["../tests"]);
+/// static NO_NOP: &[u8] = include_shader!("../tests/integration.pica", no_nop = true);
 /// ```
 ///
 /// # Errors
@@ -47,6 +80,16 @@ use quote::quote;
 /// # use citro3d_macros::include_shader;
 /// static _ERROR: &[u8] = include_shader!("../tests/bad-shader.pica");
 /// ```
+///
+/// ```compile_fail
+/// # use citro3d_macros::include_shader;
+/// static _ERROR: &[u8] = include_shader!("../tests/integration.pica", includes = "../tests");
+/// ```
+///
+/// ```compile_fail
+/// # use citro3d_macros::include_shader;
+/// static _ERROR: &[u8] = include_shader!("../tests/integration.pica", debug = true);
+/// ```
 #[proc_macro]
 pub fn include_shader(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     match include_shader_impl(input) {
@@ -61,11 +104,11 @@ pub fn include_shader(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 fn include_shader_impl(input: TokenStream) -> Result<TokenStream, Box<dyn Error>> {
     let tokens: Vec<_> = input.into_iter().collect();
 
-    if tokens.len() != 1 {
-        return Err(format!("expected exactly one input token, got {}", tokens.len()).into());
-    }
+    let Some((shader_source_filename, args)) = tokens.split_first() else {
+        return Err("expected a shader source path".into());
+    };
 
-    let shader_source_filename = &tokens[0];
+    let options = parse_options(args)?;
 
     let string_lit = match StringLit::try_from(shader_source_filename) {
         Ok(lit) => lit,
@@ -103,7 +146,27 @@ fn include_shader_impl(input: TokenStream) -> Result<TokenStream, Box<dyn Error>
         .canonicalize()
         .map_err(|err| format!("unable to resolve absolute path of shader source: {err}"))?;
 
-    let shader_out_file: PathBuf = shader_source_file.with_extension("shbin");
+    let include_dirs = options
+        .include_dirs
+        .iter()
+        .map(|dir| {
+            cwd.join(invoking_source_dir)
+                .join(dir)
+                .canonicalize()
+                .map_err(|err| format!("unable to resolve include directory {dir:?}: {err}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Each set of options and include directories gets its own output, so that
+    // compiling the same shader in different ways doesn't overwrite the cache.
+    let variant = {
+        let mut hasher = DefaultHasher::new();
+        include_dirs.hash(&mut hasher);
+        options.hash(&mut hasher);
+        hasher.finish()
+    };
+    let shader_out_file: PathBuf =
+        shader_source_file.with_extension(format!("{variant:016x}.shbin"));
 
     let out_dir = PathBuf::from(env!("OUT_DIR"));
 
@@ -116,37 +179,71 @@ fn include_shader_impl(input: TokenStream) -> Result<TokenStream, Box<dyn Error>
         .create(out_parent)
         .map_err(|err| format!("unable to create output directory {out_parent:?}: {err}"))?;
 
-    let devkitpro = PathBuf::from(env!("DEVKITPRO"));
-    let picasso = devkitpro.join("tools/bin/picasso");
+    // Skip running picasso if the shader was already compiled from the same
+    // sources and include directories, e.g. by a previous build. If an
+    // included file can't be found, always run picasso so it reports the error.
+    let source = std::fs::read(&shader_source_file)
+        .map_err(|err| format!("unable to read shader source {shader_source_file:?}: {err}"))?;
+    let included = included_files(&shader_source_file, &include_dirs);
+    let fingerprint = included
+        .as_deref()
+        .and_then(|included| fingerprint(&source, included, &include_dirs, &options));
+    let fingerprint_path = out_path.with_extension("shbin.hash");
+    let warnings_path = out_path.with_extension("shbin.warnings");
 
-    let output = process::Command::new(&picasso)
-        .arg("--out")
-        .args([&out_path, &shader_source_file])
-        .output()
-        .map_err(|err| format!("unable to run {picasso:?}: {err}"))?;
+    // The warnings are needed to reuse the shader, since they're reported again
+    let cached_warnings = std::fs::read_to_string(&warnings_path).ok().filter(|_| {
+        out_path.is_file()
+            && fingerprint.as_ref().is_some_and(|fingerprint| {
+                std::fs::read_to_string(&fingerprint_path)
+                    .is_ok_and(|cached| cached == *fingerprint)
+            })
+    });
 
-    let error_code = match output.status.code() {
-        Some(0) => None,
-        code => Some(code.map_or_else(|| String::from("<unknown>"), |c| c.to_string())),
+    let warnings = match cached_warnings {
+        Some(warnings) => warnings,
+        None => {
+            let warnings = compile_shader(&shader_source_file, &out_path, &include_dirs, &options)?;
+
+            // Failing to write these only means recompiling next time. The
+            // fingerprint goes last, since it marks the other files as valid.
+            match &fingerprint {
+                Some(fingerprint) => {
+                    let _ = write_atomic(&warnings_path, &warnings);
+                    let _ = write_atomic(&fingerprint_path, fingerprint);
+                }
+                None => {
+                    let _ = std::fs::remove_file(&fingerprint_path);
+                }
+            }
+
+            warnings
+        }
     };
 
-    if let Some(code) = error_code {
-        return Err(format!(
-            "failed to compile shader: `picasso` exited with status {code}: {}",
-            String::from_utf8_lossy(&output.stderr),
-        )
-        .into());
+    // picasso may print warnings on success, which shouldn't go unnoticed
+    if !warnings.is_empty() {
+        shader_source_filename
+            .span()
+            .warning(format!("`picasso` emitted warnings:\n{warnings}"))
+            .emit();
     }
 
     let bytes = std::fs::read(&out_path)
         .map_err(|err| format!("unable to read output file {out_path:?}: {err}"))?;
 
     let source_file_path = shader_source_file.to_string_lossy();
+    let included_file_paths = included
+        .unwrap_or_default()
+        .into_iter()
+        .map(|path| path.to_string_lossy().into_owned());
 
     let result = quote! {
         {
             // ensure the source is re-evaluted if the input file changes
             const _SOURCE: &[u8] = include_bytes! ( #source_file_path );
+            // ... or any of the files it includes
+            #( const _: &[u8] = include_bytes! ( #included_file_paths ); )*
 
             // https://users.rust-lang.org/t/can-i-conveniently-compile-bytes-into-a-rust-program-with-a-specific-alignment/24049/2
             #[repr(C)]
@@ -168,3 +265,304 @@ fn include_shader_impl(input: TokenStream) -> Result<TokenStream, Box<dyn Error>
 
     Ok(result.into())
 }
+
+/// Derive `citro3d::attrib::Vertex` for a `#[repr(C)]` struct, describing the
+/// vertex attribute each field is loaded as.
+///
+/// Each field must be annotated with `#[attrib(register = N)]`, giving the
+/// shader input register (`v0` to `v15`) it is loaded into. The format and
+/// number of elements are derived from the field type (e.g. `[f32; 3]` or
+/// `[u8; 4]`), or can be given explicitly with `format` (one of `f32`, `u8`,
+/// `i8` or `i16`) and `count` (1 to 4), which is checked against the size of
+/// the field at compile time.
+///
+/// The attribute info and stride can then be obtained with
+/// `citro3d::attrib::Info::for_vertex`.
+///
+/// # Example
+///
+/// ```ignore
+/// use citro3d::attrib::{self, Vertex};
+///
+/// #[derive(Clone, Copy, Vertex)]
+/// #[repr(C)]
+/// struct MyVertex {
+///     #[attrib(register = 0)]
+///     position: [f32; 3],
+///     #[attrib(register = 1, format = u8, count = 4)]
+///     color: u32,
+/// }
+///
+/// let attr_info = attrib::Info::for_vertex::<MyVertex>().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// The macro will fail to compile if the struct is not `#[repr(C)]`, is generic,
+/// or if a field is missing its `#[attrib]` annotation or has an invalid one.
+#[proc_macro_derive(Vertex, attributes(attrib))]
+pub fn derive_vertex(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    vertex::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Compile `source_file` to `out_path` with `picasso`, returning any warnings
+/// it printed. `out_path` is replaced atomically, so a concurrent build reading
+/// it never sees a partially written shader.
+fn compile_shader(
+    source_file: &Path,
+    out_path: &Path,
+    include_dirs: &[PathBuf],
+    options: &Options,
+) -> Result<String, Box<dyn Error>> {
+    let picasso = picasso_path()?;
+
+    let mut command = process::Command::new(&picasso);
+    for dir in include_dirs {
+        command.arg("-i").arg(dir);
+    }
+    if options.no_nop {
+        command.arg("--no-nop");
+    }
+
+    let tmp_path = temp_path(out_path);
+    let output = command
+        .arg("--out")
+        .args([tmp_path.as_path(), source_file])
+        .output()
+        .map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => format!(
+                "`picasso` was not found at {picasso:?}. Make sure the devkitPro \
+                 `3ds-dev` package group (which provides `picasso`) is installed"
+            ),
+            _ => format!("unable to run {picasso:?}: {err}"),
+        })?;
+
+    let error_code = match output.status.code() {
+        Some(0) => None,
+        code => Some(code.map_or_else(|| String::from("<unknown>"), |c| c.to_string())),
+    };
+
+    if let Some(code) = error_code {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(format!(
+            "failed to compile shader: `picasso` exited with status {code}: {}",
+            String::from_utf8_lossy(&output.stderr),
+        )
+        .into());
+    }
+
+    std::fs::rename(&tmp_path, out_path)
+        .map_err(|err| format!("unable to write output file {out_path:?}: {err}"))?;
+
+    Ok(String::from_utf8_lossy(&output.stderr).trim().to_owned())
+}
+
+/// Write `contents` to `path` by renaming a temporary file over it, so that a
+/// concurrent build reading `path` never sees a partially written file.
+fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let tmp_path = temp_path(path);
+    std::fs::write(&tmp_path, contents)?;
+    std::fs::rename(&tmp_path, path).inspect_err(|_| {
+        let _ = std::fs::remove_file(&tmp_path);
+    })
+}
+
+/// A temporary path next to `path`, unique to this process.
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}.tmp", process::id()));
+    path.with_file_name(name)
+}
+
+/// A fingerprint of the inputs to `picasso`, including the contents of the
+/// `included` files, used to detect whether a previously compiled shader can
+/// be reused. Returns `None` if an included file can't be read.
+fn fingerprint(
+    source: &[u8],
+    included: &[PathBuf],
+    include_dirs: &[PathBuf],
+    options: &Options,
+) -> Option<String> {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    for path in included {
+        path.hash(&mut hasher);
+        std::fs::read(path).ok()?.hash(&mut hasher);
+    }
+    include_dirs.hash(&mut hasher);
+    options.hash(&mut hasher);
+    Some(format!("{:016x}", hasher.finish()))
+}
+
+/// Find the files pulled in by `source_file` with `.include`, recursively.
+/// Like `picasso`, each file is looked up next to the file including it, then
+/// in `include_dirs`. Returns `None` if an included file can't be found.
+fn included_files(source_file: &Path, include_dirs: &[PathBuf]) -> Option<Vec<PathBuf>> {
+    let mut included = Vec::new();
+    let mut pending = vec![source_file.to_path_buf()];
+
+    while let Some(file) = pending.pop() {
+        let source = std::fs::read_to_string(&file).ok()?;
+        let dir = file.parent()?;
+
+        for name in source.lines().filter_map(include_directive) {
+            let path = std::iter::once(dir)
+                .chain(include_dirs.iter().map(PathBuf::as_path))
+                .map(|dir| dir.join(name))
+                .find(|path| path.is_file())?
+                .canonicalize()
+                .ok()?;
+
+            if path != source_file && !included.contains(&path) {
+                included.push(path.clone());
+                pending.push(path);
+            }
+        }
+    }
+
+    Some(included)
+}
+
+/// Get the file name from an `.include "file"` directive.
+fn include_directive(line: &str) -> Option<&str> {
+    let name = line
+        .trim_start()
+        .strip_prefix(".include")?
+        .trim_start()
+        .strip_prefix('"')?;
+    name.split('"').next()
+}
+
+/// Find the `picasso` binary in the devkitPro installation pointed to by the
+/// `DEVKITPRO` environment variable.
+///
+/// This is checked when the macro is expanded rather than when this crate is
+/// built, so a missing or misconfigured installation results in a compile
+/// error at the macro's call site.
+fn picasso_path() -> Result<PathBuf, Box<dyn Error>> {
+    let devkitpro = env::var_os("DEVKITPRO")
+        .filter(|dir| !dir.is_empty())
+        .ok_or(
+            "the `DEVKITPRO` environment variable is not set. devkitPro must be installed \
+             and `DEVKITPRO` set to its location (e.g. `/opt/devkitpro`) to compile shaders",
+        )?;
+
+    let picasso = PathBuf::from(devkitpro)
+        .join("tools/bin")
+        .join(format!("picasso{}", env::consts::EXE_SUFFIX));
+    if picasso.is_file() {
+        Ok(picasso)
+    } else {
+        Err(format!(
+            "`picasso` was not found at {picasso:?}. Make sure `DEVKITPRO` points to \
+             your devkitPro installation and the `3ds-dev` package group is installed"
+        )
+        .into())
+    }
+}
+
+/// Options for `include_shader!`, given after the shader path.
+#[derive(Debug, Default, Hash)]
+struct Options {
+    /// Directories given with `includes = [...]`, relative to the invoking file
+    include_dirs: Vec<String>,
+    /// Whether to pass `--no-nop` to picasso, given with `no_nop = true`
+    no_nop: bool,
+}
+
+/// Parse the optional arguments following the shader path, i.e. a list like
+/// `, includes = ["dir", ...], no_nop = true`, in any order.
+fn parse_options(args: &[TokenTree]) -> Result<Options, Box<dyn Error>> {
+    let mut options = Options::default();
+    let mut seen = Vec::new();
+    let mut args = args.iter();
+
+    match args.next() {
+        None => return Ok(options),
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {}
+        Some(other) => return Err(format!("expected `,` after shader path, got `{other}`").into()),
+    }
+
+    // a trailing comma is allowed after each argument
+    while let Some(name) = args.next() {
+        let name = match name {
+            TokenTree::Ident(ident) => ident.to_string(),
+            other => {
+                return Err(format!(
+                    "unexpected argument `{other}`, expected `includes = [...]` or `no_nop = ...`"
+                )
+                .into())
+            }
+        };
+
+        if seen.contains(&name) {
+            return Err(format!("`{name}` was given more than once").into());
+        }
+
+        if !matches!(args.next(), Some(TokenTree::Punct(p)) if p.as_char() == '=') {
+            return Err(format!("expected `=` after `{name}`").into());
+        }
+
+        let value = args.next();
+        match name.as_str() {
+            "includes" => options.include_dirs = parse_include_dirs(value)?,
+            "no_nop" => options.no_nop = parse_bool(&name, value)?,
+            _ => {
+                return Err(
+                    format!("unknown argument `{name}`, expected `includes` or `no_nop`").into(),
+                )
+            }
+        }
+        seen.push(name);
+
+        match args.next() {
+            None => break,
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {}
+            Some(other) => {
+                return Err(format!("expected `,` between arguments, got `{other}`").into())
+            }
+        }
+    }
+
+    Ok(options)
+}
+
+/// Parse the value of `includes = ["dir", ...]`.
+fn parse_include_dirs(value: Option<&TokenTree>) -> Result<Vec<String>, Box<dyn Error>> {
+    let list = match value {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => group.stream(),
+        _ => return Err("expected a list of include directories, like `[\"dir\"]`".into()),
+    };
+
+    let mut dirs = Vec::new();
+    let mut list = list.into_iter();
+    while let Some(token) = list.next() {
+        let dir = StringLit::try_from(&token)
+            .map_err(|err| format!("include directory must be a string literal: {err}"))?;
+        dirs.push(dir.value().to_string());
+
+        match list.next() {
+            None => break,
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {}
+            Some(other) => {
+                return Err(
+                    format!("expected `,` between include directories, got `{other}`").into(),
+                )
+            }
+        }
+    }
+
+    Ok(dirs)
+}
+
+/// Parse the value of a boolean argument like `no_nop = true`.
+fn parse_bool(name: &str, value: Option<&TokenTree>) -> Result<bool, Box<dyn Error>> {
+    match value {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "true" => Ok(true),
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "false" => Ok(false),
+        _ => Err(format!("expected `true` or `false` after `{name} =`").into()),
+    }
+}
@@ -0,0 +1,218 @@
+//! Implementation of `#[derive(Vertex)]`.
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Field, Ident, LitInt};
+
+/// The number of shader input registers.
+const REGISTER_COUNT: u16 = 16;
+
+pub fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    check_repr_c(input)?;
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "`Vertex` cannot be derived for generic types",
+        ));
+    }
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`Vertex` can only be derived for structs",
+        ));
+    };
+
+    let ty = &input.ident;
+    let mut attributes = Vec::new();
+    let mut size_checks = Vec::new();
+
+    for (i, field) in data.fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = syn::Index::from(i);
+                quote!(#index)
+            }
+        };
+        let field_ty = &field.ty;
+        let FieldAttrib {
+            register,
+            format_count,
+        } = FieldAttrib::parse(field)?;
+
+        let (format, count) = match format_count {
+            Some((format, count)) => {
+                let message = format!(
+                    "the size of field `{member}` does not match its `#[attrib]` format and count"
+                );
+                size_checks.push(quote_spanned! {field.span()=>
+                    assert!(
+                        ::core::mem::size_of::<#field_ty>()
+                            == ::citro3d::attrib::Format::#format.size() * #count as usize,
+                        #message,
+                    );
+                });
+                (quote!(::citro3d::attrib::Format::#format), quote!(#count))
+            }
+            None => (
+                quote_spanned! {field_ty.span()=>
+                    <#field_ty as ::citro3d::attrib::AttributeData>::FORMAT
+                },
+                quote_spanned! {field_ty.span()=>
+                    <#field_ty as ::citro3d::attrib::AttributeData>::COUNT
+                },
+            ),
+        };
+
+        attributes.push(quote! {
+            ::citro3d::attrib::VertexAttribute {
+                register: #register,
+                format: #format,
+                count: #count,
+                offset: ::core::mem::offset_of!(#ty, #member),
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl ::citro3d::attrib::Vertex for #ty {
+            const ATTRIBUTES: &'static [::citro3d::attrib::VertexAttribute] = &[
+                #(#attributes),*
+            ];
+        }
+
+        const _: () = {
+            #(#size_checks)*
+        };
+    })
+}
+
+/// Require `#[repr(C)]`, since the GPU reads the fields in declaration order.
+fn check_repr_c(input: &DeriveInput) -> syn::Result<()> {
+    let mut repr_c = false;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") {
+                repr_c = true;
+            } else if meta.input.peek(syn::token::Paren) {
+                // e.g. `align(4)`
+                let _args;
+                syn::parenthesized!(_args in meta.input);
+            }
+            Ok(())
+        })?;
+    }
+
+    if repr_c {
+        Ok(())
+    } else {
+        Err(syn::Error::new_spanned(
+            &input.ident,
+            "`Vertex` can only be derived for `#[repr(C)]` structs, \
+             since the field order must match the attribute order",
+        ))
+    }
+}
+
+/// The contents of a field's `#[attrib(...)]` annotation.
+struct FieldAttrib {
+    register: u16,
+    /// The format variant and element count, if given explicitly instead of
+    /// being derived from the field's type
+    format_count: Option<(Ident, u8)>,
+}
+
+impl FieldAttrib {
+    fn parse(field: &Field) -> syn::Result<Self> {
+        let Some(attr) = field
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("attrib"))
+        else {
+            return Err(syn::Error::new(
+                field.span(),
+                "missing `#[attrib(register = ...)]` annotation",
+            ));
+        };
+
+        let mut register = None;
+        let mut format = None;
+        let mut count = None;
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("register") {
+                let lit: LitInt = meta.value()?.parse()?;
+                let value = lit.base10_parse::<u16>()?;
+                if value >= REGISTER_COUNT {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        format!("register must be less than {REGISTER_COUNT}"),
+                    ));
+                }
+                register = Some(value);
+            } else if meta.path.is_ident("format") {
+                let ident: Ident = meta.value()?.parse()?;
+                format = Some(format_variant(&ident)?);
+            } else if meta.path.is_ident("count") {
+                let lit: LitInt = meta.value()?.parse()?;
+                let value = lit.base10_parse::<u8>()?;
+                if !(1..=4).contains(&value) {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        "count must be between 1 and 4",
+                    ));
+                }
+                count = Some(value);
+            } else {
+                return Err(meta.error("expected `register`, `format` or `count`"));
+            }
+            Ok(())
+        })?;
+
+        let Some(register) = register else {
+            return Err(syn::Error::new_spanned(attr, "missing `register = ...`"));
+        };
+
+        let format_count = match (format, count) {
+            (Some(format), Some(count)) => Some((format, count)),
+            (None, None) => None,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "`format` and `count` must be given together",
+                ))
+            }
+        };
+
+        Ok(Self {
+            register,
+            format_count,
+        })
+    }
+}
+
+/// Map a format given as a Rust scalar type (or `attrib::Format` variant) to
+/// the corresponding `attrib::Format` variant.
+fn format_variant(ident: &Ident) -> syn::Result<Ident> {
+    let variant = match ident.to_string().as_str() {
+        "f32" | "Float" => "Float",
+        "u8" | "UnsignedByte" => "UnsignedByte",
+        "i8" | "Byte" => "Byte",
+        "i16" | "Short" => "Short",
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "expected one of `f32`, `u8`, `i8` or `i16`",
+            ))
+        }
+    };
+    Ok(Ident::new(variant, ident.span()))
+}
@@ -0,0 +1,4 @@
+; Aliases shared with include.pica
+
+.alias inpos v1
+.alias inclr v0
@@ -13,3 +13,25 @@ fn includes_shader_const() {
 
     assert_eq!(SHADER_BYTES.len() % 4, 0);
 }
+
+#[test]
+fn includes_shader_with_include_dirs() {
+    static SHADER_BYTES: &[u8] = include_shader!("integration.pica", includes = ["."]);
+
+    assert_eq!(SHADER_BYTES.len() % 4, 0);
+}
+
+#[test]
+fn includes_shader_with_options() {
+    static SHADER_BYTES: &[u8] =
+        include_shader!("integration.pica", no_nop = true, includes = ["."],);
+
+    assert_eq!(SHADER_BYTES.len() % 4, 0);
+}
+
+#[test]
+fn includes_shader_with_include_directive() {
+    static SHADER_BYTES: &[u8] = include_shader!("include.pica", includes = ["include"]);
+
+    assert_eq!(SHADER_BYTES.len() % 4, 0);
+}
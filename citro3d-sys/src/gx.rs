@@ -3,6 +3,11 @@
 
 use ctru_sys::{GX_TRANSFER_FORMAT, GX_TRANSFER_SCALE};
 
+#[inline]
+pub fn GX_BUFFER_DIM(width: u32, height: u32) -> u32 {
+    (height << 16) | (width & 0xFFFF)
+}
+
 #[inline]
 pub fn GX_TRANSFER_FLIP_VERT(flip: bool) -> u32 {
     flip as u32
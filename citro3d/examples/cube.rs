@@ -5,11 +5,12 @@
 
 use citro3d::macros::include_shader;
 use citro3d::math::{
-    AspectRatio, ClipPlanes, CoordinateOrientation, FVec3, Matrix4, Projection, StereoDisplacement,
+    Angle, AspectRatio, ClipPlanes, CoordinateOrientation, FVec3, Matrix4, Projection,
+    StereoDisplacement,
 };
 use citro3d::render::ClearFlags;
+use citro3d::texenv;
 use citro3d::{attrib, buffer, render, shader};
-use citro3d::{texenv, IndexType};
 use ctru::prelude::*;
 use ctru::services::gfx::{RawFrameBuffer, Screen, TopScreen3D};
 
@@ -79,7 +80,7 @@ fn main() {
     let mut hid = Hid::new().expect("Couldn't obtain HID controller");
     let apt = Apt::new().expect("Couldn't obtain APT controller");
 
-    let mut instance = citro3d::Instance::new().expect("failed to initialize Citro3D");
+    let mut instance = citro3d::Instance::new(&gfx).expect("failed to initialize Citro3D");
 
     let top_screen = TopScreen3D::from(&gfx.top_screen);
 
@@ -104,7 +105,7 @@ fn main() {
 
     let program = shader::Program::new(vertex_shader).unwrap();
     instance.bind_program(&program);
-    let mut vbo_data = Vec::with_capacity_in(VERTS.len(), ctru::linear::LinearAllocator);
+    let mut vbo_data = buffer::LinearVec::with_capacity(VERTS.len());
     for vert in VERTS.iter().enumerate().map(|(i, v)| Vertex {
         pos: Vec3 {
             x: v[0],
@@ -134,7 +135,7 @@ fn main() {
         FVec3::new(0.0, 1.0, 0.0),
         CoordinateOrientation::RightHanded,
     );
-    let indecies_a = [
+    let indecies_a: [u16; 36] = [
         0, 3, 1, 1, 3, 2, // triangles making up the top (+y) facing side.
         4, 5, 7, 5, 6, 7, // bottom (-y)
         8, 11, 9, 9, 11, 10, // right (+x)
@@ -144,6 +145,7 @@ fn main() {
     ];
     let mut indecies = Vec::with_capacity_in(indecies_a.len(), ctru::linear::LinearAllocator);
     indecies.extend_from_slice(&indecies_a);
+    let index_buffer = buffer::IndexBuffer::new(&indecies[..]).unwrap();
 
     while apt.main_loop() {
         hid.scan_input();
@@ -167,11 +169,7 @@ fn main() {
 
                 instance.set_attr_info(&attr_info);
                 unsafe {
-                    instance.draw_elements(
-                        buffer::Primitive::Triangles,
-                        &buf_info,
-                        IndexType::U16(&indecies),
-                    );
+                    instance.draw_elements(buffer::Primitive::Triangles, &buf_info, &index_buffer);
                 }
 
                 //instance.draw_arrays(buffer::Primitive::Triangles, vbo_data);
@@ -192,7 +190,7 @@ fn main() {
 
 fn prepare_vbos<'a>(
     buf_info: &'a mut buffer::Info,
-    vbo_data: &'a [Vertex],
+    vbo_data: &'a buffer::LinearVec<Vertex>,
 ) -> (attrib::Info, buffer::Slice<'a>) {
     // Configure attributes for use with the vertex shader
     let mut attr_info = attrib::Info::new();
@@ -225,7 +223,7 @@ fn calculate_projections() -> Projections {
     let slider_val = ctru::os::current_3d_slider_state();
     let interocular_distance = slider_val / 2.0;
 
-    let vertical_fov = 40.0_f32.to_radians();
+    let vertical_fov = Angle::from_degrees(40.0);
     let screen_depth = 2.0;
 
     let clip_planes = ClipPlanes {
@@ -1,4 +1,3 @@
-#![feature(allocator_api)]
 use std::f32::consts::PI;
 use std::sync::Arc;
 
@@ -6,7 +5,7 @@ use citro3d::{
     attrib, buffer,
     light::{FresnelSelector, LightEnv, LightLut, LightLutDistAtten, LightLutId, LutInput},
     material::{Color, Material},
-    math::{AspectRatio, ClipPlanes, FVec3, FVec4, Matrix4, Projection, StereoDisplacement},
+    math::{Angle, AspectRatio, ClipPlanes, FVec3, FVec4, Matrix4, Projection, StereoDisplacement},
     render::{self, ClearFlags},
     shader, texenv,
 };
@@ -255,7 +254,7 @@ fn main() {
     let mut hid = Hid::new().expect("Couldn't obtain HID controller");
     let apt = Apt::new().expect("Couldn't obtain APT controller");
 
-    let mut instance = citro3d::Instance::new().expect("failed to initialize Citro3D");
+    let mut instance = citro3d::Instance::new(&gfx).expect("failed to initialize Citro3D");
 
     let top_screen = TopScreen3D::from(&gfx.top_screen);
 
@@ -296,8 +295,7 @@ fn main() {
     let program = Arc::pin(shader::Program::new(vertex_shader).unwrap());
     instance.bind_program(program.clone());
 
-    let mut vbo_data = Vec::with_capacity_in(VERTICES.len(), ctru::linear::LinearAllocator);
-    vbo_data.extend_from_slice(VERTICES);
+    let vbo_data = buffer::LinearVec::from_slice(VERTICES);
 
     let mut buf_info = buffer::Info::new();
     let (attr_info, vbo_data) = prepare_vbos(&mut buf_info, &vbo_data);
@@ -376,14 +374,14 @@ fn main() {
             render_to(&mut bottom_target, &center);
         });
         c.translate(0.0, 0.0, 2.0);
-        c.rotate_y(1.0f32.to_radians());
+        c.rotate_y(Angle::from_degrees(1.0));
         c.translate(0.0, 0.0, -2.0);
     }
 }
 
 fn prepare_vbos<'a>(
     buf_info: &'a mut buffer::Info,
-    vbo_data: &'a [Vertex],
+    vbo_data: &'a buffer::LinearVec<Vertex>,
 ) -> (attrib::Info, buffer::Slice<'a>) {
     // Configure attributes for use with the vertex shader
     let mut attr_info = attrib::Info::new();
@@ -421,7 +419,7 @@ fn calculate_projections() -> Projections {
     let slider_val = ctru::os::current_3d_slider_state();
     let interocular_distance = slider_val / 2.0;
 
-    let vertical_fov = 40.0_f32.to_radians();
+    let vertical_fov = Angle::from_degrees(40.0);
     let screen_depth = 2.0;
 
     let clip_planes = ClipPlanes {
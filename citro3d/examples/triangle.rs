@@ -1,10 +1,8 @@
 //! This example demonstrates the most basic usage of `citro3d`: rendering a simple
 //! RGB triangle (sometimes called a "Hello triangle") to the 3DS screen.
 
-#![feature(allocator_api)]
-
 use citro3d::macros::include_shader;
-use citro3d::math::{AspectRatio, ClipPlanes, Matrix4, Projection, StereoDisplacement};
+use citro3d::math::{Angle, AspectRatio, ClipPlanes, Matrix4, Projection, StereoDisplacement};
 use citro3d::render::ClearFlags;
 use citro3d::texenv;
 use citro3d::{attrib, buffer, render, shader};
@@ -58,7 +56,7 @@ fn main() {
     let mut hid = Hid::new().expect("Couldn't obtain HID controller");
     let apt = Apt::new().expect("Couldn't obtain APT controller");
 
-    let mut instance = citro3d::Instance::new().expect("failed to initialize Citro3D");
+    let mut instance = citro3d::Instance::new(&gfx).expect("failed to initialize Citro3D");
 
     let top_screen = TopScreen3D::from(&gfx.top_screen);
 
@@ -84,8 +82,7 @@ fn main() {
     let program = shader::Program::new(vertex_shader).unwrap();
     instance.bind_program(&program);
 
-    let mut vbo_data = Vec::with_capacity_in(VERTICES.len(), ctru::linear::LinearAllocator);
-    vbo_data.extend_from_slice(VERTICES);
+    let vbo_data = buffer::LinearVec::from_slice(VERTICES);
 
     let mut buf_info = buffer::Info::new();
     let (attr_info, vbo_data) = prepare_vbos(&mut buf_info, &vbo_data);
@@ -137,7 +134,7 @@ fn main() {
 
 fn prepare_vbos<'a>(
     buf_info: &'a mut buffer::Info,
-    vbo_data: &'a [Vertex],
+    vbo_data: &'a buffer::LinearVec<Vertex>,
 ) -> (attrib::Info, buffer::Slice<'a>) {
     // Configure attributes for use with the vertex shader
     let mut attr_info = attrib::Info::new();
@@ -170,7 +167,7 @@ fn calculate_projections() -> Projections {
     let slider_val = ctru::os::current_3d_slider_state();
     let interocular_distance = slider_val / 2.0;
 
-    let vertical_fov = 40.0_f32.to_radians();
+    let vertical_fov = Angle::from_degrees(40.0);
     let screen_depth = 2.0;
 
     let clip_planes = ClipPlanes {
@@ -5,11 +5,53 @@
 //!
 //! See the [`buffer`](crate::buffer) module to use the vertex data itself.
 
+use std::fmt;
 use std::mem::MaybeUninit;
 
+/// Derive [`Vertex`] for a `#[repr(C)]` struct, annotating each field with the
+/// shader input register it is loaded into.
+///
+/// # Example
+///
+/// ```
+/// # let _runner = test_runner::GdbRunner::default();
+/// use citro3d::attrib::{self, Vertex};
+///
+/// #[derive(Clone, Copy, Vertex)]
+/// #[repr(C)]
+/// struct MyVertex {
+///     #[attrib(register = 0)]
+///     position: [f32; 3],
+///     #[attrib(register = 2)]
+///     tex_coord: [f32; 2],
+///     #[attrib(register = 1, format = u8, count = 4)]
+///     color: u32,
+/// }
+///
+/// let attr_info = attrib::Info::for_vertex::<MyVertex>().unwrap();
+/// assert_eq!(attr_info.stride(), std::mem::size_of::<MyVertex>());
+/// ```
+///
+/// Structs without `#[repr(C)]` are rejected, since their field order is not
+/// guaranteed:
+///
+/// ```compile_fail
+/// # use citro3d::attrib::Vertex;
+/// #[derive(Clone, Copy, Vertex)]
+/// struct MyVertex {
+///     #[attrib(register = 0)]
+///     position: [f32; 3],
+/// }
+/// ```
+pub use citro3d_macros::Vertex;
+
 /// Vertex attribute info. This struct describes how vertex buffers are
 /// layed out and used (i.e. the shape of the vertex data).
-#[derive(Debug, Clone, Copy)]
+///
+/// The registered attributes can be inspected with [`Info::attributes`], and
+/// the [`Debug`] output lists them, which is helpful to diagnose draws with
+/// garbled or missing geometry.
+#[derive(Clone, Copy)]
 #[doc(alias = "C3D_AttrInfo")]
 pub struct Info(pub(crate) citro3d_sys::C3D_AttrInfo);
 
@@ -17,7 +59,7 @@ pub struct Info(pub(crate) citro3d_sys::C3D_AttrInfo);
 /// (e.g. position or color). These are called `v0`, `v1`, ... `v15` in the
 /// [picasso](https://github.com/devkitPro/picasso/blob/master/Manual.md)
 /// shader language.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Register(libc::c_int);
 
 impl Register {
@@ -38,12 +80,18 @@ impl Register {
 /// An attribute index. This is the attribute's actual index in the input buffer,
 /// and may correspond to any [`Register`] (or multiple) as input in the shader
 /// program.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Index(u8);
 
+impl From<Index> for libc::c_int {
+    fn from(value: Index) -> Self {
+        value.0.into()
+    }
+}
+
 /// The data format of an attribute.
 #[repr(u32)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[doc(alias = "GPU_FORMATS")]
 pub enum Format {
     /// A signed byte, i.e. [`i8`].
@@ -56,6 +104,74 @@ pub enum Format {
     Short = ctru_sys::GPU_SHORT,
 }
 
+impl Format {
+    /// Get the size in bytes of a single element of this format.
+    pub const fn size(self) -> usize {
+        match self {
+            Self::Byte | Self::UnsignedByte => 1,
+            Self::Short => 2,
+            Self::Float => 4,
+        }
+    }
+
+    fn from_bits(bits: u32) -> Self {
+        match bits & 0b11 {
+            ctru_sys::GPU_BYTE => Self::Byte,
+            ctru_sys::GPU_UNSIGNED_BYTE => Self::UnsignedByte,
+            ctru_sys::GPU_SHORT => Self::Short,
+            _ => Self::Float,
+        }
+    }
+}
+
+/// A single attribute registered in an [`Info`]. See [`Info::attributes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attribute {
+    index: Index,
+    register: Register,
+    source: Source,
+}
+
+/// Where the value of an [`Attribute`] comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// Read from the vertex buffer as `count` elements of the given `format`.
+    Buffer {
+        /// The data format of each element.
+        format: Format,
+        /// The number of elements, from 1 to 4.
+        count: u8,
+    },
+    /// A fixed value shared by every vertex. See [`Info::add_fixed`].
+    Fixed,
+}
+
+impl Attribute {
+    /// The index of the attribute, i.e. its registration order.
+    pub fn index(&self) -> Index {
+        self.index
+    }
+
+    /// The shader input register the attribute is loaded into.
+    pub fn register(&self) -> Register {
+        self.register
+    }
+
+    /// Where the value of the attribute comes from.
+    pub fn source(&self) -> Source {
+        self.source
+    }
+
+    /// The size in bytes of the attribute in each vertex. Fixed attributes are
+    /// not stored in the vertex buffer, so their size is 0.
+    pub fn size(&self) -> usize {
+        match self.source {
+            Source::Buffer { format, count } => format.size() * usize::from(count),
+            Source::Fixed => 0,
+        }
+    }
+}
+
 // SAFETY: the RWLock ensures unique access when mutating the global struct, and
 // we trust citro3d to Do The Right Thing™ and not mutate it otherwise.
 unsafe impl Sync for Info {}
@@ -79,6 +195,35 @@ impl Info {
         Self::default()
     }
 
+    /// Start building an attribute info with a [`Builder`], which validates the
+    /// attributes as they are added and reports the [`Index`] assigned to each.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use citro3d::attrib::{Format, Info, Register};
+    /// # fn main() -> citro3d::Result<()> {
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// let mut builder = Info::builder();
+    /// let position = builder.add(Register::new(0)?, Format::Float, 3)?;
+    /// let color = builder.add(Register::new(1)?, Format::UnsignedByte, 4)?;
+    /// let attr_info = builder.build();
+    ///
+    /// assert_eq!(attr_info.attr_count(), 2);
+    /// assert_eq!(i32::from(position), 0);
+    /// assert_eq!(i32::from(color), 1);
+    ///
+    /// // Each register can only be used once
+    /// let mut builder = Info::builder();
+    /// builder.add(Register::new(0)?, Format::Float, 3)?;
+    /// assert!(builder.add(Register::new(0)?, Format::Float, 2).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
     pub(crate) fn copy_from(raw: *const citro3d_sys::C3D_AttrInfo) -> Option<Self> {
         if raw.is_null() {
             None
@@ -127,6 +272,25 @@ impl Info {
         Ok(Index(idx))
     }
 
+    /// Add a fixed attribute to the attribute info. Instead of being read from a
+    /// vertex buffer, a fixed attribute has the same value for every vertex, set with
+    /// [`Instance::set_fixed_attribute`](crate::Instance::set_fixed_attribute).
+    ///
+    /// # Errors
+    ///
+    /// If this attribute info already has the maximum number of attributes.
+    #[doc(alias = "AttrInfo_AddFixed")]
+    pub fn add_fixed(&mut self, register: Register) -> crate::Result<Index> {
+        // SAFETY: same as `add_loader`
+        let ret = unsafe { citro3d_sys::AttrInfo_AddFixed(&mut self.0, register.0) };
+
+        let Ok(idx) = ret.try_into() else {
+            return Err(crate::Error::TooManyAttributes);
+        };
+
+        Ok(Index(idx))
+    }
+
     pub fn permutation(&self) -> u64 {
         self.0.permutation
     }
@@ -135,4 +299,394 @@ impl Info {
     pub fn attr_count(&self) -> libc::c_int {
         self.0.attrCount
     }
+
+    /// Get the size in bytes of a single vertex laid out according to this info,
+    /// without any padding between attributes. Fixed attributes are not stored
+    /// in the vertex buffer and don't count towards the stride.
+    pub fn stride(&self) -> usize {
+        self.attributes().map(|attr| attr.size()).sum()
+    }
+
+    /// Iterate over the registered attributes, in registration order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use citro3d::attrib::{Format, Info, Register, Source};
+    /// # fn main() -> citro3d::Result<()> {
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// let mut builder = Info::builder();
+    /// builder.add(Register::new(0)?, Format::Float, 3)?;
+    /// builder.add_fixed(Register::new(1)?)?;
+    /// let attr_info = builder.build();
+    ///
+    /// let attrs: Vec<_> = attr_info.attributes().collect();
+    /// assert_eq!(attrs.len(), 2);
+    /// assert_eq!(attrs[0].register(), Register::new(0)?);
+    /// assert_eq!(attrs[0].source(), Source::Buffer { format: Format::Float, count: 3 });
+    /// assert_eq!(attrs[1].register(), Register::new(1)?);
+    /// assert_eq!(attrs[1].source(), Source::Fixed);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute> + '_ {
+        (0..self.0.attrCount as usize).map(|i| {
+            let source = if self.0.flags[1] & (1 << (16 + i)) != 0 {
+                Source::Fixed
+            } else {
+                // Each attribute is 4 bits: 2 for the format, and 2 for the count - 1
+                let bits = if i < 8 {
+                    self.0.flags[0] >> (i * 4)
+                } else {
+                    self.0.flags[1] >> ((i - 8) * 4)
+                };
+                Source::Buffer {
+                    format: Format::from_bits(bits),
+                    count: ((bits >> 2) & 0b11) as u8 + 1,
+                }
+            };
+
+            Attribute {
+                index: Index(i as u8),
+                register: Register(((self.0.permutation >> (i * 4)) & 0xF) as libc::c_int),
+                source,
+            }
+        })
+    }
+}
+
+impl Info {
+    /// Build the attribute info for a vertex type, registering its attributes in
+    /// the order they are laid out in memory. See [`Vertex`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidAlignment`](crate::Error::InvalidAlignment) if there is
+    ///   padding between the attributes. The GPU reads attributes packed one
+    ///   after the other, so fields must be ordered or sized to avoid padding.
+    /// * Any error of [`Builder::add`], e.g. if two attributes use the same register.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use std::mem::offset_of;
+    /// use citro3d::attrib::{self, Vertex, VertexAttribute};
+    ///
+    /// #[derive(Clone, Copy)]
+    /// #[repr(C)]
+    /// struct MyVertex {
+    ///     position: [f32; 3],
+    ///     color: [u8; 3],
+    /// }
+    ///
+    /// impl Vertex for MyVertex {
+    ///     const ATTRIBUTES: &'static [VertexAttribute] = &[
+    ///         VertexAttribute::new::<[f32; 3]>(0, offset_of!(MyVertex, position)),
+    ///         VertexAttribute::new::<[u8; 3]>(1, offset_of!(MyVertex, color)),
+    ///     ];
+    /// }
+    ///
+    /// let attr_info = attrib::Info::for_vertex::<MyVertex>().unwrap();
+    /// assert_eq!(attr_info.attr_count(), 2);
+    /// assert_eq!(attr_info.stride(), 15);
+    /// ```
+    pub fn for_vertex<V: Vertex>() -> crate::Result<Self> {
+        let mut builder = Self::builder();
+        let mut offset = 0;
+        for attr in V::ATTRIBUTES {
+            if attr.offset != offset {
+                return Err(crate::Error::InvalidAlignment);
+            }
+            offset += attr.format.size() * usize::from(attr.count);
+            builder.add(Register::new(attr.register)?, attr.format, attr.count)?;
+        }
+        Ok(builder.build())
+    }
+}
+
+/// A vertex type whose layout can be described as a list of attributes, so its
+/// [`Info`] can be built with [`Info::for_vertex`] instead of registering each
+/// attribute by hand.
+///
+/// Implementors should be `#[repr(C)]` so their field order is fixed.
+pub trait Vertex: Copy {
+    /// The attributes of the vertex, in the order they are laid out in memory.
+    const ATTRIBUTES: &'static [VertexAttribute];
+}
+
+/// The layout of a single attribute of a [`Vertex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VertexAttribute {
+    /// The shader input register the attribute is loaded into, from 0 to 15.
+    pub register: u16,
+    /// The data format of each element.
+    pub format: Format,
+    /// The number of elements, from 1 to 4.
+    pub count: u8,
+    /// The offset of the attribute within the vertex, in bytes.
+    pub offset: usize,
+}
+
+impl VertexAttribute {
+    /// Describe an attribute of type `T`, loaded into `register`, at `offset`
+    /// bytes from the start of the vertex.
+    pub const fn new<T: AttributeData>(register: u16, offset: usize) -> Self {
+        Self {
+            register,
+            format: T::FORMAT,
+            count: T::COUNT,
+            offset,
+        }
+    }
+}
+
+/// A type which can be loaded as a single vertex attribute: a scalar, or an
+/// array of up to 4 scalars (e.g. `[f32; 3]` for a position or `[u8; 4]` for
+/// an RGBA color).
+pub trait AttributeData: Copy {
+    /// The data format of each element.
+    const FORMAT: Format;
+    /// The number of elements.
+    const COUNT: u8;
+}
+
+macro_rules! impl_attribute_data {
+    ($($ty:ty => $format:ident),* $(,)?) => {
+        $(
+            impl AttributeData for $ty {
+                const FORMAT: Format = Format::$format;
+                const COUNT: u8 = 1;
+            }
+
+            impl AttributeData for [$ty; 1] {
+                const FORMAT: Format = Format::$format;
+                const COUNT: u8 = 1;
+            }
+
+            impl AttributeData for [$ty; 2] {
+                const FORMAT: Format = Format::$format;
+                const COUNT: u8 = 2;
+            }
+
+            impl AttributeData for [$ty; 3] {
+                const FORMAT: Format = Format::$format;
+                const COUNT: u8 = 3;
+            }
+
+            impl AttributeData for [$ty; 4] {
+                const FORMAT: Format = Format::$format;
+                const COUNT: u8 = 4;
+            }
+        )*
+    };
+}
+
+impl_attribute_data! {
+    i8 => Byte,
+    u8 => UnsignedByte,
+    i16 => Short,
+    f32 => Float,
+}
+
+impl fmt::Debug for Info {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Info")
+            .field("attributes", &self.attributes().collect::<Vec<_>>())
+            .field("stride", &self.stride())
+            .finish()
+    }
+}
+
+/// A builder for [`Info`], checking that the resulting attribute layout is valid.
+/// See [`Info::builder`].
+///
+/// Attributes are assigned an [`Index`] in the order they are added, starting at 0,
+/// which is also the order their data must appear in each vertex. The index is
+/// returned when adding each attribute, e.g. to set the value of a fixed
+/// attribute with [`Instance::set_fixed_attribute`](crate::Instance::set_fixed_attribute).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Builder {
+    info: Info,
+    /// Bitmask of the registers already in use
+    registers: u16,
+}
+
+impl Builder {
+    /// Add an attribute loader, reading `count` elements of the given `format`
+    /// from the vertex buffer into `register`, and return the attribute's index.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidSize`](crate::Error::InvalidSize) if `count` is not between 1 and 4.
+    /// * [`Error::DuplicateRegister`](crate::Error::DuplicateRegister) if `register`
+    ///   is already used by another attribute.
+    /// * [`Error::TooManyAttributes`](crate::Error::TooManyAttributes) if the maximum
+    ///   number (12) of attributes has already been added.
+    #[doc(alias = "AttrInfo_AddLoader")]
+    pub fn add(&mut self, register: Register, format: Format, count: u8) -> crate::Result<Index> {
+        if count == 0 {
+            return Err(crate::Error::InvalidSize);
+        }
+        self.check_register(register)?;
+        let index = self.info.add_loader(register, format, count)?;
+        self.use_register(register);
+        Ok(index)
+    }
+
+    /// Add a fixed attribute for `register`, and return the attribute's index.
+    /// See [`Info::add_fixed`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::DuplicateRegister`](crate::Error::DuplicateRegister) if `register`
+    ///   is already used by another attribute.
+    /// * [`Error::TooManyAttributes`](crate::Error::TooManyAttributes) if the maximum
+    ///   number (12) of attributes has already been added.
+    #[doc(alias = "AttrInfo_AddFixed")]
+    pub fn add_fixed(&mut self, register: Register) -> crate::Result<Index> {
+        self.check_register(register)?;
+        let index = self.info.add_fixed(register)?;
+        self.use_register(register);
+        Ok(index)
+    }
+
+    /// Finish building the attribute info. The attributes were already validated
+    /// as they were added, so this can't fail.
+    pub fn build(&self) -> Info {
+        self.info
+    }
+
+    fn check_register(&self, register: Register) -> crate::Result<()> {
+        if self.registers & (1 << register.0) != 0 {
+            Err(crate::Error::DuplicateRegister)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn use_register(&mut self, register: Register) {
+        self.registers |= 1 << register.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mixed_format_stride() {
+        let mut builder = Info::builder();
+        builder
+            .add(Register::new(0).unwrap(), Format::Short, 3)
+            .unwrap();
+        builder
+            .add(Register::new(1).unwrap(), Format::Byte, 3)
+            .unwrap();
+        builder
+            .add(Register::new(2).unwrap(), Format::UnsignedByte, 4)
+            .unwrap();
+        let fixed = builder.add_fixed(Register::new(3).unwrap()).unwrap();
+        builder
+            .add(Register::new(4).unwrap(), Format::Float, 2)
+            .unwrap();
+        let info = builder.build();
+
+        assert_eq!(fixed, Index(3));
+
+        assert_eq!(info.attr_count(), 5);
+        assert_eq!(info.stride(), 3 * 2 + 3 + 4 + 2 * 4);
+    }
+
+    #[test]
+    fn attributes_round_trip() {
+        let mut builder = Info::builder();
+        builder
+            .add(Register::new(3).unwrap(), Format::Short, 2)
+            .unwrap();
+        builder.add_fixed(Register::new(0).unwrap()).unwrap();
+        builder
+            .add(Register::new(9).unwrap(), Format::UnsignedByte, 4)
+            .unwrap();
+        let info = builder.build();
+
+        let attrs: Vec<_> = info.attributes().collect();
+        assert_eq!(attrs.len(), 3);
+
+        assert_eq!(attrs[0].index(), Index(0));
+        assert_eq!(attrs[0].register(), Register::new(3).unwrap());
+        assert_eq!(
+            attrs[0].source(),
+            Source::Buffer {
+                format: Format::Short,
+                count: 2
+            }
+        );
+
+        assert_eq!(attrs[1].register(), Register::new(0).unwrap());
+        assert_eq!(attrs[1].source(), Source::Fixed);
+
+        assert_eq!(attrs[2].index(), Index(2));
+        assert_eq!(attrs[2].register(), Register::new(9).unwrap());
+        assert_eq!(
+            attrs[2].source(),
+            Source::Buffer {
+                format: Format::UnsignedByte,
+                count: 4
+            }
+        );
+    }
+
+    #[test]
+    fn for_vertex_layout() {
+        #[derive(Clone, Copy)]
+        #[repr(C)]
+        struct Packed {
+            position: [f32; 3],
+            normal: [i16; 3],
+            color: [u8; 2],
+        }
+
+        impl Vertex for Packed {
+            const ATTRIBUTES: &'static [VertexAttribute] = &[
+                VertexAttribute::new::<[f32; 3]>(0, std::mem::offset_of!(Packed, position)),
+                VertexAttribute::new::<[i16; 3]>(2, std::mem::offset_of!(Packed, normal)),
+                VertexAttribute::new::<[u8; 2]>(1, std::mem::offset_of!(Packed, color)),
+            ];
+        }
+
+        #[derive(Clone, Copy)]
+        #[repr(C)]
+        struct Padded {
+            color: [u8; 3],
+            position: [f32; 3],
+        }
+
+        impl Vertex for Padded {
+            const ATTRIBUTES: &'static [VertexAttribute] = &[
+                VertexAttribute::new::<[u8; 3]>(0, std::mem::offset_of!(Padded, color)),
+                VertexAttribute::new::<[f32; 3]>(1, std::mem::offset_of!(Padded, position)),
+            ];
+        }
+
+        let info = Info::for_vertex::<Packed>().unwrap();
+        assert_eq!(info.stride(), std::mem::size_of::<Packed>());
+        assert_eq!(info.attributes().nth(1).unwrap().register(), Register(2));
+
+        assert!(matches!(
+            Info::for_vertex::<Padded>(),
+            Err(crate::Error::InvalidAlignment)
+        ));
+    }
+
+    #[test]
+    fn stride_with_many_attributes() {
+        let mut builder = Info::builder();
+        for i in 0..10 {
+            builder
+                .add(Register::new(i).unwrap(), Format::Float, 4)
+                .unwrap();
+        }
+        assert_eq!(builder.build().stride(), 10 * 4 * 4);
+    }
 }
@@ -3,29 +3,43 @@
 //! See the [`attrib`] module for details on how to describe the shape and type
 //! of the VBO data.
 
+use std::fmt;
 use std::mem::MaybeUninit;
+use std::ops::{Deref, DerefMut, Range};
 
-use crate::attrib;
+use ctru::linear::LinearAllocator;
+
+use crate::util::{flush_data_cache, is_linear_ptr};
+use crate::{attrib, IndexType};
+
+/// The maximum number of vertex buffers which can be registered in an [`Info`].
+pub const MAX_BUFFERS: usize = 12;
 
 /// Vertex buffer info. This struct is used to describe the shape of the buffer
 /// data to be sent to the GPU for rendering.
-#[derive(Debug, Clone, Copy)]
+///
+/// An info can hold up to [`MAX_BUFFERS`] buffers, each containing some (or all)
+/// of the vertex attributes. The registered buffers can be inspected with
+/// [`Info::buffers`].
+#[derive(Clone, Copy)]
 #[doc(alias = "C3D_BufInfo")]
 pub struct Info(pub(crate) citro3d_sys::C3D_BufInfo);
 
 /// A slice of buffer data. This borrows the buffer data and can be thought of
 /// as similar to `&[T]` obtained by slicing a `Vec<T>`.
-#[derive(Debug, Clone, Copy)]
+#[derive(Clone, Copy)]
 pub struct Slice<'buf> {
     index: libc::c_int,
     size: libc::c_int,
     buf_info: &'buf Info,
+    vbo_data: &'buf [u8],
+    stride: usize,
     // TODO: should we encapsulate the primitive here too, and require it when the
     // slice is registered? Could there ever be a use case to draw different primitives
     // using the same backing data???
 }
 
-impl Slice<'_> {
+impl<'buf> Slice<'buf> {
     /// Get the index into the buffer for this slice.
     pub fn index(&self) -> libc::c_int {
         self.index
@@ -46,24 +60,600 @@ impl Slice<'_> {
     pub fn info(&self) -> &Info {
         self.buf_info
     }
+
+    /// Narrow this slice to the vertices in `range`, relative to the start of
+    /// the slice. This allows packing many meshes in a single buffer and drawing
+    /// them individually, without registering the buffer again.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SliceOutOfBounds`](crate::Error::SliceOutOfBounds) if
+    /// `range` is decreasing or extends past the end of this slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::{attrib, buffer};
+    /// # use citro3d::buffer::LinearVec;
+    /// # let mut attr_info = attrib::Info::new();
+    /// # attr_info
+    /// #     .add_loader(attrib::Register::new(0).unwrap(), attrib::Format::Float, 3)
+    /// #     .unwrap();
+    /// let vertices = LinearVec::from_slice(&[[0.0_f32; 3]; 300]);
+    ///
+    /// let mut buf_info = buffer::Info::new();
+    /// let all = buf_info.add(&vertices, &attr_info).unwrap();
+    ///
+    /// let mesh = all.sub(100..250).unwrap();
+    /// assert_eq!(mesh.index(), 100);
+    /// assert_eq!(mesh.len(), 150);
+    ///
+    /// assert!(all.sub(250..400).is_err());
+    /// ```
+    pub fn sub(&self, range: Range<usize>) -> crate::Result<Slice<'buf>> {
+        let len = self.size.max(0) as usize;
+        if range.start > range.end || range.end > len {
+            return Err(crate::Error::SliceOutOfBounds { range, len });
+        }
+
+        Ok(Slice {
+            // both fit in the parent slice, so they fit in a c_int too
+            index: self.index + range.start as libc::c_int,
+            size: range.len() as libc::c_int,
+            buf_info: self.buf_info,
+            vbo_data: self.vbo_data,
+            stride: self.stride,
+        })
+    }
+
+    /// Flush the CPU data cache for the vertex data of this slice.
+    ///
+    /// Linear memory is cached by the CPU but read directly by the GPU, so
+    /// after the CPU writes vertex data (e.g. filling or updating a buffer),
+    /// it must be flushed before drawing or the GPU may read stale data.
+    /// Data which is only written once before being registered only needs
+    /// to be flushed once.
+    ///
+    /// Only the vertices of this slice are flushed, from the buffer it was
+    /// created from; when using [`Info::add_with_attributes`], flush the slice
+    /// returned for each buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::System`](crate::Error::System) if the flush failed.
+    #[doc(alias = "GSPGPU_FlushDataCache")]
+    pub fn flush(&self) -> crate::Result<()> {
+        let start = self.index.max(0) as usize * self.stride;
+        let end = start + self.size.max(0) as usize * self.stride;
+        flush_data_cache(&self.vbo_data[start..end])
+    }
+}
+
+impl fmt::Debug for Slice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Slice")
+            .field("index", &self.index)
+            .field("len", &self.size)
+            .field("info", self.buf_info)
+            .finish()
+    }
+}
+
+/// A growable array allocated in linear memory, suitable for vertex data
+/// (or indices) read directly by the GPU.
+///
+/// This is a thin wrapper around a [`Vec`] using [`ctru::linear::LinearAllocator`],
+/// which dereferences to a slice and frees its memory when dropped, so it can be
+/// used without enabling the `allocator_api` feature. Vertex data must be in a
+/// `LinearVec` to be registered with [`Info::add`], so it is always in linear
+/// memory. Slices of a `LinearVec` can also be used with [`IndexBuffer::new`].
+///
+/// # Example
+///
+/// ```
+/// # let _runner = test_runner::GdbRunner::default();
+/// # use citro3d::{attrib, buffer};
+/// use citro3d::buffer::LinearVec;
+///
+/// let mut attr_info = attrib::Info::new();
+/// attr_info
+///     .add_loader(attrib::Register::new(0).unwrap(), attrib::Format::Float, 3)
+///     .unwrap();
+///
+/// let vertices = LinearVec::from_slice(&[[0.0_f32, 0.5, 0.5], [-0.5, -0.5, 0.5], [0.5, -0.5, 0.5]]);
+///
+/// let mut buf_info = buffer::Info::new();
+/// let slice = buf_info.add(&vertices, &attr_info).unwrap();
+/// assert_eq!(slice.len(), 3);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct LinearVec<T>(Vec<T, LinearAllocator>);
+
+impl<T> LinearVec<T> {
+    /// Create a new, empty `LinearVec`. No memory is allocated until elements
+    /// are added.
+    pub fn new() -> Self {
+        Self(Vec::new_in(LinearAllocator))
+    }
+
+    /// Create a new, empty `LinearVec` with space for at least `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity_in(capacity, LinearAllocator))
+    }
+
+    /// Create a `LinearVec` containing a copy of the given elements.
+    pub fn from_slice(data: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        let mut vec = Self::with_capacity(data.len());
+        vec.0.extend_from_slice(data);
+        vec
+    }
+
+    /// Append an element to the end of the vector.
+    pub fn push(&mut self, value: T) {
+        self.0.push(value);
+    }
+
+    /// Remove all elements from the vector, keeping its allocated memory.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Get the underlying [`Vec`].
+    pub fn into_inner(self) -> Vec<T, LinearAllocator> {
+        self.0
+    }
+}
+
+impl<T> Default for LinearVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Deref for LinearVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for LinearVec<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> Extend<T> for LinearVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+impl<T> FromIterator<T> for LinearVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<T> From<Vec<T, LinearAllocator>> for LinearVec<T> {
+    fn from(vec: Vec<T, LinearAllocator>) -> Self {
+        Self(vec)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for LinearVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// A double-buffered vertex buffer for geometry which changes every frame.
+///
+/// The GPU may still be reading the vertex data of the previous frame while the
+/// CPU prepares the next one, so overwriting the same buffer can corrupt what
+/// is drawn. A `DynamicBuffer` keeps two [`LinearVec`]s and alternates between
+/// them on each [`write`](Self::write), so the buffer written to is never the
+/// one used by the frame in flight.
+///
+/// Data should be written at most once per frame, e.g. once in each
+/// [`Instance::render_frame_with`](crate::Instance::render_frame_with) call.
+///
+/// # Example
+///
+/// ```
+/// # let _runner = test_runner::GdbRunner::default();
+/// # use citro3d::{attrib, buffer};
+/// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+/// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+/// # let mut attr_info = attrib::Info::new();
+/// # attr_info
+/// #     .add_loader(attrib::Register::new(0).unwrap(), attrib::Format::Float, 2)
+/// #     .unwrap();
+/// let mut vertices = buffer::DynamicBuffer::new();
+///
+/// for frame in 0..2 {
+///     let offset = frame as f32 * 0.1;
+///     let data = vertices
+///         .write(&[[offset, 0.5], [-0.5, -0.5], [0.5, -0.5]])
+///         .unwrap();
+///
+///     let mut buf_info = buffer::Info::new();
+///     let slice = buf_info.add(data, &attr_info).unwrap();
+///
+///     instance.render_frame_with(|instance| {
+///         // bind a program and draw `slice`...
+///         # let _ = slice;
+///     });
+/// }
+/// ```
+#[derive(Debug)]
+pub struct DynamicBuffer<T> {
+    buffers: [LinearVec<T>; 2],
+    current: usize,
+}
+
+impl<T> DynamicBuffer<T> {
+    /// Create a new, empty dynamic buffer.
+    pub fn new() -> Self {
+        Self {
+            buffers: [LinearVec::new(), LinearVec::new()],
+            current: 0,
+        }
+    }
+
+    /// Create a new dynamic buffer with space for at least `capacity` elements
+    /// in each of its buffers.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buffers: [
+                LinearVec::with_capacity(capacity),
+                LinearVec::with_capacity(capacity),
+            ],
+            current: 0,
+        }
+    }
+
+    /// Switch to the buffer not used by the previous write, and replace its
+    /// contents with `data`. The CPU data cache is flushed for the written
+    /// data, so the returned buffer can be registered with [`Info::add`]
+    /// and drawn right away.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::System`](crate::Error::System) if flushing the data
+    /// cache failed.
+    pub fn write(&mut self, data: &[T]) -> crate::Result<&LinearVec<T>>
+    where
+        T: Clone,
+    {
+        self.current ^= 1;
+
+        let buffer = &mut self.buffers[self.current];
+        buffer.clear();
+        buffer.extend(data.iter().cloned());
+
+        flush_data_cache(&buffer[..])?;
+        Ok(buffer)
+    }
+
+    /// The data from the most recent [`write`](Self::write).
+    pub fn current(&self) -> &LinearVec<T> {
+        &self.buffers[self.current]
+    }
+}
+
+impl<T> Default for DynamicBuffer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// The geometric primitive to draw (i.e. what shapes the buffer data describes).
+///
+/// These are all the primitive types supported by the PICA200. Note that the
+/// hardware has no primitive restart, so there is no special index value to
+/// start a new strip or fan: separate strips must either be drawn with separate
+/// draw calls, or joined into a single strip using degenerate triangles.
 #[repr(u32)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[doc(alias = "GPU_Primitive_t")]
 pub enum Primitive {
     /// Draw triangles (3 vertices per triangle).
+    #[doc(alias = "GPU_TRIANGLES")]
     Triangles = ctru_sys::GPU_TRIANGLES,
     /// Draw a triangle strip (each vertex shared by 1-3 triangles).
+    #[doc(alias = "GPU_TRIANGLE_STRIP")]
+    #[doc(alias = "GPU_TRISTRIP")]
     TriangleStrip = ctru_sys::GPU_TRIANGLE_STRIP,
     /// Draw a triangle fan (first vertex shared by all triangles).
+    #[doc(alias = "GPU_TRIANGLE_FAN")]
+    #[doc(alias = "GPU_TRIFAN")]
     TriangleFan = ctru_sys::GPU_TRIANGLE_FAN,
     /// Geometry primitive. Can be used for more complex use cases like geometry
     /// shaders that output custom primitives.
+    ///
+    /// The vertices are passed to the geometry shader in groups of the size
+    /// configured with [`Program::with_geometry`](crate::shader::Program::with_geometry),
+    /// without being assembled into triangles first. For example, the PICA200
+    /// has no line primitive or polygon mode, but a wireframe can be drawn by
+    /// passing the edges from [`wireframe_indices`] two vertices at a time to
+    /// a geometry shader which emits each line as a thin quad.
+    #[doc(alias = "GPU_GEOMETRY_PRIM")]
     GeometryPrim = ctru_sys::GPU_GEOMETRY_PRIM,
 }
 
+/// Convert indices of [`Primitive::Triangles`] into pairs of indices, one
+/// pair for each edge of each triangle, e.g. to draw a wireframe of the mesh
+/// with [`Primitive::GeometryPrim`].
+///
+/// Edges shared by adjacent triangles are included once per triangle. Any
+/// trailing indices that don't form a whole triangle are ignored.
+///
+/// # Example
+///
+/// ```
+/// # let _runner = test_runner::GdbRunner::default();
+/// # use citro3d::buffer::{self, IndexBuffer};
+/// let quad: [u16; 6] = [0, 1, 2, 2, 1, 3];
+/// let lines = buffer::wireframe_indices(&quad);
+/// assert_eq!(*lines, [0, 1, 1, 2, 2, 0, 2, 1, 1, 3, 3, 2]);
+///
+/// let index_buffer = IndexBuffer::new(&*lines).unwrap();
+/// ```
+pub fn wireframe_indices<T: Copy>(triangles: &[T]) -> LinearVec<T> {
+    let mut lines = LinearVec::with_capacity(triangles.len() * 2);
+    lines.extend(
+        triangles
+            .chunks_exact(3)
+            .flat_map(|tri| [tri[0], tri[1], tri[1], tri[2], tri[2], tri[0]]),
+    );
+    lines
+}
+
+/// The data type of the indices in an [`IndexBuffer`].
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[doc(alias = "C3D_UNSIGNED_BYTE")]
+#[doc(alias = "C3D_UNSIGNED_SHORT")]
+pub enum IndexFormat {
+    /// Indices are unsigned bytes, i.e. [`u8`].
+    UnsignedByte = citro3d_sys::C3D_UNSIGNED_BYTE,
+    /// Indices are unsigned shorts, i.e. [`u16`].
+    UnsignedShort = citro3d_sys::C3D_UNSIGNED_SHORT,
+}
+
+/// A buffer of vertex indices to be used for indexed drawing with
+/// [`Instance::draw_elements`](crate::Instance::draw_elements).
+///
+/// The GPU reads indices directly from memory, so the backing data must be
+/// allocated with the [`ctru::linear`] allocator. [`IndexBuffer::new`] borrows
+/// indices which already are, while [`IndexBuffer::new_u16`] and
+/// [`IndexBuffer::new_u8`] copy them into a new linear allocation owned by
+/// the buffer.
+///
+/// An `IndexBuffer` can be copied with [`IndexBuffer::try_clone`].
+#[derive(Debug)]
+pub struct IndexBuffer<'idx> {
+    indices: Indices<'idx>,
+}
+
+/// The storage of an [`IndexBuffer`].
+#[derive(Debug)]
+enum Indices<'idx> {
+    Borrowed(IndexType<'idx>),
+    U16(LinearVec<u16>),
+    U8(LinearVec<u8>),
+}
+
+impl Indices<'_> {
+    fn as_index_type(&self) -> IndexType<'_> {
+        match self {
+            Self::Borrowed(indices) => *indices,
+            Self::U16(indices) => IndexType::U16(indices),
+            Self::U8(indices) => IndexType::U8(indices),
+        }
+    }
+}
+
+impl IndexBuffer<'static> {
+    /// Copy `u16` indices into a new buffer allocated in linear memory, and
+    /// flush them from the CPU cache so they are visible to the GPU.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::System`](crate::Error::System) if the cache flush failed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::buffer::{IndexBuffer, IndexFormat};
+    /// let index_buffer = IndexBuffer::new_u16(&[0, 1, 2, 2, 1, 3]).unwrap();
+    /// assert_eq!(index_buffer.format(), IndexFormat::UnsignedShort);
+    /// assert_eq!(index_buffer.len(), 6);
+    /// ```
+    #[doc(alias = "linearAlloc")]
+    pub fn new_u16(indices: &[u16]) -> crate::Result<Self> {
+        Self::new_owned(Indices::U16(LinearVec::from_slice(indices)))
+    }
+
+    /// Copy `u8` indices into a new buffer allocated in linear memory. This
+    /// uses half as much memory as [`Self::new_u16`], but can only address
+    /// the first 256 vertices.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::new_u16`].
+    #[doc(alias = "linearAlloc")]
+    pub fn new_u8(indices: &[u8]) -> crate::Result<Self> {
+        Self::new_owned(Indices::U8(LinearVec::from_slice(indices)))
+    }
+
+    fn new_owned(indices: Indices<'static>) -> crate::Result<Self> {
+        let buffer = Self { indices };
+        buffer.flush()?;
+        Ok(buffer)
+    }
+}
+
+impl<'idx> IndexBuffer<'idx> {
+    /// Wrap the given indices for use in an indexed draw call.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidMemoryLocation`](crate::Error::InvalidMemoryLocation)
+    /// if `indices` is not allocated with the [`ctru::linear`] allocator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![feature(allocator_api)]
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::buffer::{IndexBuffer, IndexFormat};
+    /// let mut indices = Vec::new_in(ctru::linear::LinearAllocator);
+    /// indices.extend_from_slice(&[0_u16, 1, 2]);
+    ///
+    /// let index_buffer = IndexBuffer::new(&indices[..]).unwrap();
+    /// assert_eq!(index_buffer.format(), IndexFormat::UnsignedShort);
+    /// assert_eq!(index_buffer.len(), 3);
+    /// ```
+    pub fn new(indices: impl Into<IndexType<'idx>>) -> crate::Result<Self> {
+        let indices = indices.into();
+        if is_linear_ptr(indices.as_ptr()) {
+            Ok(Self {
+                indices: Indices::Borrowed(indices),
+            })
+        } else {
+            Err(crate::Error::InvalidMemoryLocation)
+        }
+    }
+
+    /// Copy the index buffer. A buffer which owns its indices copies them into a
+    /// new linear allocation, which is flushed the same way as by
+    /// [`IndexBuffer::new_u16`]. Copying one which borrows its indices is cheap.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::System`](crate::Error::System) if flushing the copied
+    /// indices failed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::buffer::IndexBuffer;
+    /// let index_buffer = IndexBuffer::new_u8(&[0, 1, 2]).unwrap();
+    /// let copy = index_buffer.try_clone().unwrap();
+    /// assert_eq!(copy.len(), index_buffer.len());
+    /// assert_eq!(copy.format(), index_buffer.format());
+    /// ```
+    pub fn try_clone(&self) -> crate::Result<Self> {
+        let indices = match &self.indices {
+            Indices::Borrowed(indices) => {
+                return Ok(Self {
+                    indices: Indices::Borrowed(*indices),
+                })
+            }
+            Indices::U16(indices) => Indices::U16(indices.clone()),
+            Indices::U8(indices) => Indices::U8(indices.clone()),
+        };
+
+        let buffer = Self { indices };
+        // The copy was written by the CPU, so the GPU may not see it otherwise
+        buffer.flush()?;
+        Ok(buffer)
+    }
+
+    /// Get the number of indices in the buffer.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.indices.as_index_type().len()
+    }
+
+    /// Return whether or not the buffer has any indices.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the data type of the indices in this buffer.
+    pub fn format(&self) -> IndexFormat {
+        self.indices.as_index_type().format()
+    }
+
+    /// Flush the CPU data cache for the indices, so that CPU writes to them
+    /// are visible to the GPU. See [`Slice::flush`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::System`](crate::Error::System) if the flush failed.
+    #[doc(alias = "GSPGPU_FlushDataCache")]
+    pub fn flush(&self) -> crate::Result<()> {
+        flush_data_cache(self.indices.as_index_type().as_bytes())
+    }
+
+    pub(crate) fn as_ptr(&self) -> *const libc::c_void {
+        self.indices.as_index_type().as_ptr()
+    }
+}
+
+/// The configuration of a single vertex buffer registered in an [`Info`].
+/// See [`Info::buffers`].
+#[doc(alias = "C3D_BufCfg")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferConfig {
+    offset: u32,
+    stride: u32,
+    attr_count: u8,
+    permutation: u64,
+}
+
+impl BufferConfig {
+    /// The offset of the buffer's physical address from the base address of
+    /// the [`Info`], in bytes.
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// The size in bytes of each vertex in the buffer.
+    pub fn stride(&self) -> u32 {
+        self.stride
+    }
+
+    /// The number of attributes stored in the buffer.
+    pub fn attr_count(&self) -> u8 {
+        self.attr_count
+    }
+
+    /// Which attributes are stored in the buffer, in order: each nibble is an
+    /// [`attrib::Index`], starting from the least significant.
+    pub fn permutation(&self) -> u64 {
+        self.permutation
+    }
+
+    /// Iterate over the indices of the attributes stored in the buffer, in the
+    /// order they appear in each vertex.
+    pub fn attributes(&self) -> impl Iterator<Item = u8> {
+        let permutation = self.permutation;
+        (0..self.attr_count).map(move |i| ((permutation >> (4 * i)) & 0xF) as u8)
+    }
+}
+
+impl fmt::Debug for Info {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Info")
+            .field("base_paddr", &format_args!("{:#x}", self.0.base_paddr))
+            .field("buffers", &self.buffers().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
 impl Default for Info {
     #[doc(alias = "BufInfo_Init")]
     fn default() -> Self {
@@ -92,27 +682,73 @@ impl Info {
         }
     }
 
+    /// Get the number of vertex buffers registered in this info.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.bufCount as usize
+    }
+
+    /// Return whether or not any vertex buffers are registered in this info.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over the configurations of the registered vertex buffers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::attrib::{Format, Info as AttrInfo, Register};
+    /// # use citro3d::buffer::{Info, LinearVec};
+    /// let mut builder = AttrInfo::builder();
+    /// builder.add(Register::new(0).unwrap(), Format::Float, 3).unwrap();
+    /// let attr_info = builder.build();
+    /// let vertices = LinearVec::from_slice(&[[0.0_f32; 3]; 3]);
+    ///
+    /// let mut buf_info = Info::new();
+    /// buf_info.add(&vertices, &attr_info).unwrap();
+    ///
+    /// let configs: Vec<_> = buf_info.buffers().collect();
+    /// assert_eq!(configs.len(), 1);
+    /// assert_eq!(configs[0].offset(), 0);
+    /// assert_eq!(configs[0].stride(), 12);
+    /// assert_eq!(configs[0].attr_count(), 1);
+    /// assert!(configs[0].attributes().eq([0]));
+    /// ```
+    pub fn buffers(&self) -> impl Iterator<Item = BufferConfig> + '_ {
+        self.0.buffers[..self.len()].iter().map(|buf| BufferConfig {
+            offset: buf.offset,
+            // See `BufInfo_Add` for the layout of the flags
+            stride: (buf.flags[1] >> 16) & 0xFF,
+            attr_count: (buf.flags[1] >> 28) as u8,
+            permutation: u64::from(buf.flags[0]) | (u64::from(buf.flags[1] & 0xFFFF) << 32),
+        })
+    }
+
     /// Register vertex buffer object data. The resulting [`Slice`] will have its
     /// lifetime tied to both this [`Info`] and the passed-in VBO. `vbo_data` is
     /// assumed to use one `T` per drawn primitive, and its layout is assumed to
     /// match the given `attrib_info`
     ///
-    /// # Errors
+    /// To spread the attributes over multiple buffers, use [`Info::add_with_attributes`]
+    /// instead.
     ///
-    /// Registering VBO data may fail:
+    /// # Errors
     ///
-    /// * if `vbo_data` is not allocated with the [`ctru::linear`] allocator
-    /// * if the maximum number (12) of VBOs are already registered
+    /// Returns [`Error::TooManyBuffers`](crate::Error::TooManyBuffers) if the
+    /// maximum number ([`MAX_BUFFERS`]) of VBOs are already registered.
     #[doc(alias = "BufInfo_Add")]
     pub fn add<'this, 'vbo, 'idx, T>(
         &'this mut self,
-        vbo_data: &'vbo [T],
+        vbo_data: &'vbo LinearVec<T>,
         attrib_info: &attrib::Info,
     ) -> crate::Result<Slice<'idx>>
     where
         'this: 'idx,
         'vbo: 'idx,
     {
+        let vbo_data: &[T] = vbo_data;
         unsafe {
             self.add_bytes(
                 std::slice::from_raw_parts(
@@ -125,6 +761,80 @@ impl Info {
         }
     }
 
+    /// Register vertex buffer object data containing only some of the attributes,
+    /// so that attributes can be spread over multiple buffers (e.g. positions and
+    /// texture coordinates in separate VBOs). Each `T` in `vbo_data` holds
+    /// the given `attributes`, in order.
+    ///
+    /// All buffers registered in an [`Info`] are used by a draw call, so the
+    /// [`Slice`] returned by the last call can be used to draw them all.
+    ///
+    /// # Errors
+    ///
+    /// Registering VBO data may fail:
+    ///
+    /// * if the maximum number ([`MAX_BUFFERS`]) of VBOs are already registered
+    /// * if more than 12 `attributes` are given
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::{attrib, buffer};
+    /// # use citro3d::buffer::LinearVec;
+    /// let mut attr_info = attrib::Info::new();
+    /// let position = attr_info
+    ///     .add_loader(attrib::Register::new(0).unwrap(), attrib::Format::Float, 3)
+    ///     .unwrap();
+    /// let tex_coord = attr_info
+    ///     .add_loader(attrib::Register::new(1).unwrap(), attrib::Format::Float, 2)
+    ///     .unwrap();
+    ///
+    /// let positions = LinearVec::from_slice(&[[0.0_f32, 0.5, 0.5], [-0.5, -0.5, 0.5], [0.5, -0.5, 0.5]]);
+    /// let tex_coords = LinearVec::from_slice(&[[0.5_f32, 1.0], [0.0, 0.0], [1.0, 0.0]]);
+    ///
+    /// let mut buf_info = buffer::Info::new();
+    /// buf_info.add_with_attributes(&positions, &[position]).unwrap();
+    /// let slice = buf_info.add_with_attributes(&tex_coords, &[tex_coord]).unwrap();
+    ///
+    /// assert_eq!(slice.info().len(), 2);
+    /// assert_eq!(slice.len(), 3);
+    /// ```
+    #[doc(alias = "BufInfo_Add")]
+    pub fn add_with_attributes<'this, 'vbo, 'idx, T>(
+        &'this mut self,
+        vbo_data: &'vbo LinearVec<T>,
+        attributes: &[attrib::Index],
+    ) -> crate::Result<Slice<'idx>>
+    where
+        'this: 'idx,
+        'vbo: 'idx,
+    {
+        // Each attribute in the buffer is one nibble in the permutation
+        if attributes.len() > 12 {
+            return Err(crate::Error::TooManyAttributes);
+        }
+        let permutation = attributes
+            .iter()
+            .enumerate()
+            .fold(0_u64, |perm, (i, &idx)| {
+                perm | (libc::c_int::from(idx) as u64) << (4 * i)
+            });
+
+        let vbo_data: &[T] = vbo_data;
+        unsafe {
+            self.add_raw(
+                std::slice::from_raw_parts(
+                    vbo_data.as_ptr().cast(),
+                    std::mem::size_of_val(vbo_data),
+                ),
+                std::mem::size_of::<T>() as u32,
+                attributes.len() as libc::c_int,
+                permutation,
+            )
+        }
+    }
+
     /// Add vbo bytes directly
     ///
     /// This is the same as [`Info::add`] except it requires manually specifying the
@@ -133,7 +843,8 @@ impl Info {
     ///
     /// # Safety
     /// `vbo_data` must have data matching `attrib_info` every `stride` bytes or strangeness
-    /// will occur
+    /// will occur, and must be allocated with the [`ctru::linear`] allocator (e.g.
+    /// be a [`LinearVec`]).
     #[doc(alias = "BufInfo_Add")]
     pub unsafe fn add_bytes<'this, 'vbo, 'idx>(
         &'this mut self,
@@ -145,6 +856,34 @@ impl Info {
         'this: 'idx,
         'vbo: 'idx,
     {
+        unsafe {
+            self.add_raw(
+                vbo_data,
+                stride,
+                attrib_info.attr_count(),
+                attrib_info.permutation(),
+            )
+        }
+    }
+
+    /// # Safety
+    /// Same as [`Info::add_bytes`], with `attr_count` and `permutation` describing
+    /// which attributes are in `vbo_data`.
+    unsafe fn add_raw<'this, 'vbo, 'idx>(
+        &'this mut self,
+        vbo_data: &'vbo [u8],
+        stride: u32,
+        attr_count: libc::c_int,
+        permutation: u64,
+    ) -> crate::Result<Slice<'idx>>
+    where
+        'this: 'idx,
+        'vbo: 'idx,
+    {
+        if stride == 0 {
+            return Err(crate::Error::InvalidSize);
+        }
+
         // SAFETY: the lifetime of the VBO data is encapsulated in the return value's
         // 'vbo lifetime, and the pointer to &mut self.0 is used to access values
         // in the BufInfo, not copied to be used later.
@@ -153,20 +892,24 @@ impl Info {
                 &mut self.0,
                 vbo_data.as_ptr().cast(),
                 stride as isize,
-                attrib_info.attr_count(),
-                attrib_info.permutation(),
+                attr_count,
+                permutation,
             )
         };
 
-        // Error codes from <https://github.com/devkitPro/citro3d/blob/master/source/buffers.c#L11>
+        // Error codes from <https://github.com/devkitPro/citro3d/blob/master/source/buffers.c#L11>.
+        // -2 means the data isn't in linear memory, which callers must ensure.
         match res {
-            ..=-3 => Err(crate::Error::System(res)),
-            -2 => Err(crate::Error::InvalidMemoryLocation),
+            ..=-2 => Err(crate::Error::System(res)),
             -1 => Err(crate::Error::TooManyBuffers),
+            // `res` is the index of the buffer within the info, but the slice
+            // always starts at the first vertex
             _ => Ok(Slice {
-                index: res,
-                size: vbo_data.len().try_into()?,
+                index: 0,
+                size: (vbo_data.len() / stride as usize).try_into()?,
                 buf_info: self,
+                vbo_data,
+                stride: stride as usize,
             }),
         }
     }
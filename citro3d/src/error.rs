@@ -2,8 +2,14 @@
 
 use std::ffi::NulError;
 use std::num::TryFromIntError;
+use std::ops::Range;
 use std::sync::TryLockError;
 
+use bytemuck::PodCastError;
+
+use crate::render::{ColorFormat, DepthFormat, TestFunc};
+use crate::texture::TexFormat;
+
 /// The common result type returned by `citro3d` functions.
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -20,6 +26,76 @@ pub enum Error {
     /// A C3D object or context could not be initialized.
     #[error("a C3D object or context failed to initialize")]
     FailedToInitialize,
+    /// The `citro3d` context could not be initialized, e.g. because the command
+    /// buffer could not be allocated or `citro3d` is already initialized.
+    #[error("failed to initialize citro3d with a command buffer of {cmdbuf_size} bytes")]
+    InitFailed {
+        /// The requested command buffer size, in bytes.
+        cmdbuf_size: usize,
+    },
+    /// The requested command buffer size is not supported.
+    /// See [`Instance::with_cmdbuf_size`](crate::Instance::with_cmdbuf_size).
+    #[error(
+        "invalid command buffer size {size}, must be a non-zero multiple of {} bytes",
+        crate::Instance::CMDBUF_ALIGNMENT
+    )]
+    InvalidCmdBufSize {
+        /// The requested command buffer size, in bytes.
+        size: usize,
+    },
+    /// A render target could not be created, usually because there was not
+    /// enough VRAM for its buffers.
+    #[error(
+        "failed to create {width}x{height} render target \
+        (color format {color_format:?}, depth format {depth_format:?})"
+    )]
+    RenderTargetCreationFailed {
+        /// The requested width of the target.
+        width: usize,
+        /// The requested height of the target.
+        height: usize,
+        /// The requested color format of the target.
+        color_format: ColorFormat,
+        /// The requested depth format of the target.
+        depth_format: Option<DepthFormat>,
+    },
+    /// The given comparison function is not supported by the early depth test.
+    #[error("comparison function {0:?} cannot be used for the early depth test")]
+    UnsupportedEarlyDepthFunc(TestFunc),
+    /// The given texture format cannot be rendered to.
+    #[error("texture format {0:?} cannot be used as a render target")]
+    UnsupportedRenderTargetFormat(TexFormat),
+    /// A texture could not be created, usually because there was not enough
+    /// memory for its data.
+    #[error("failed to create {width}x{height} texture (format {format:?}, in VRAM: {vram})")]
+    TextureCreationFailed {
+        /// The requested width of the texture.
+        width: u16,
+        /// The requested height of the texture.
+        height: u16,
+        /// The requested format of the texture.
+        format: TexFormat,
+        /// Whether the texture was to be allocated in VRAM.
+        vram: bool,
+    },
+    /// A texture's dimensions are not supported by the GPU.
+    /// See [`Tex::validate_dimensions`](crate::texture::Tex::validate_dimensions).
+    #[error(
+        "invalid texture size {width}x{height}, dimensions must be powers of two \
+        between {} and {}",
+        crate::limits::MIN_TEXTURE_SIZE,
+        crate::limits::MAX_TEXTURE_SIZE
+    )]
+    InvalidTextureDimensions {
+        /// The requested width of the texture.
+        width: u16,
+        /// The requested height of the texture.
+        height: u16,
+    },
+    /// A mipmap operation was attempted on a texture created without mipmap levels.
+    /// See [`TexParams::max_level`](crate::texture::TexParams::max_level).
+    #[error("texture has no mipmap levels")]
+    NoMipmapLevels,
     /// A size parameter was specified that cannot be converted to the proper type.
     #[error("specified size parameter is invalid")]
     InvalidSize,
@@ -33,9 +109,15 @@ pub enum Error {
     /// Indicates that too many vertex attributes were registered (max 12 supported).
     #[error("too many vertex attribute requested (max of 12)")]
     TooManyAttributes,
+    /// The same shader input register was used for more than one vertex attribute.
+    #[error("shader input register is already used by another vertex attribute")]
+    DuplicateRegister,
     /// Indicates that too many vertex buffer objects were registered (max 12 supported).
     #[error("too many vertex buffer objects registered (max of 12)")]
     TooManyBuffers,
+    /// The given data is not sufficiently aligned for its intended use.
+    #[error("data is not properly aligned")]
+    InvalidAlignment,
     /// The given memory could not be converted to a physical address for sharing
     /// with the GPU. Data should be allocated with [`ctru::linear`].
     #[error("invalid memory location, address should be allocated with `ctru::linear`")]
@@ -43,6 +125,37 @@ pub enum Error {
     /// The given name was not valid for the requested purpose.
     #[error("provided name is invalid")]
     InvalidName,
+    /// A uniform was bound to an index outside the valid range for its type.
+    /// See [`Uniform::index_range`](crate::uniform::Uniform::index_range).
+    #[error("uniform index {index:#x} is out of the valid range {valid:#x?}")]
+    InvalidUniformIndex {
+        /// The index the uniform was bound to.
+        index: u8,
+        /// The valid range of indices for the uniform's type.
+        valid: Range<u8>,
+    },
+    /// A uniform was bound to an index where it would not fit in the remaining
+    /// registers for its type.
+    #[error(
+        "uniform of length {len} at index {index:#x} overflows the registers ending at {end:#x}"
+    )]
+    UniformOverflow {
+        /// The index the uniform was bound to.
+        index: u8,
+        /// The number of registers the uniform occupies.
+        len: usize,
+        /// The end (exclusive) of the valid range of indices for the uniform's type.
+        end: u8,
+    },
+    /// A sub-range of a [`buffer::Slice`](crate::buffer::Slice) was out of
+    /// the slice's bounds.
+    #[error("range {range:?} is out of bounds for a buffer slice of length {len}")]
+    SliceOutOfBounds {
+        /// The requested range, relative to the start of the slice.
+        range: Range<usize>,
+        /// The length of the slice.
+        len: usize,
+    },
     /// The requested resource could not be found.
     #[error("requested resource could not be found")]
     NotFound,
@@ -54,6 +167,18 @@ impl From<TryFromIntError> for Error {
     }
 }
 
+impl From<PodCastError> for Error {
+    fn from(err: PodCastError) -> Self {
+        match err {
+            PodCastError::TargetAlignmentGreaterAndInputNotAligned
+            | PodCastError::AlignmentMismatch => Self::InvalidAlignment,
+            PodCastError::OutputSliceWouldHaveSlop | PodCastError::SizeMismatch => {
+                Self::InvalidSize
+            }
+        }
+    }
+}
+
 impl<T> From<TryLockError<T>> for Error {
     fn from(_: TryLockError<T>) -> Self {
         Self::LockHeld
@@ -0,0 +1,185 @@
+//! Fog, as applied by the fixed-function fragment pipeline.
+//!
+//! Fog blends fragments towards a fixed color based on their depth. How much
+//! of the fog color is used for a given depth is decided by a [`FogLut`],
+//! which is bound with [`Instance::bind_fog_lut`](crate::Instance::bind_fog_lut).
+//!
+//! Fog is enabled or disabled with [`Instance::set_fog_mode`](crate::Instance::set_fog_mode),
+//! which leaves the bound LUT and color as they are. This makes it cheap to
+//! turn fog off for part of a frame (e.g. UI) and back on again.
+//!
+//! # Example
+//!
+//! ```
+//! # let _runner = test_runner::GdbRunner::default();
+//! # use citro3d::fog::{FogLut, FogMode};
+//! # use citro3d::math::ClipPlanes;
+//! # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+//! let mut instance = citro3d::Instance::new(&gfx).unwrap();
+//!
+//! let clip_planes = ClipPlanes {
+//!     near: 0.01,
+//!     far: 100.0,
+//! };
+//! instance.bind_fog_lut(Some(FogLut::exponential(0.05, 2.0, clip_planes)));
+//! instance.set_fog_color(0x404040);
+//! instance.set_fog_mode(FogMode::Fog, false);
+//!
+//! // draw the world...
+//!
+//! instance.set_fog_mode(FogMode::Disabled, false);
+//!
+//! // draw the UI...
+//! ```
+
+use std::mem::MaybeUninit;
+
+use crate::math::ClipPlanes;
+
+/// Number of depth steps in a [`FogLut`].
+const FOG_LUT_STEPS: usize = 128;
+
+/// Lookup table mapping fragment depth to a fog factor.
+///
+/// A factor of `1.0` means no fog is applied, and `0.0` means the fragment is
+/// fully covered by the fog color.
+#[doc(alias = "C3D_FogLut")]
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct FogLut(citro3d_sys::C3D_FogLut);
+
+impl FogLut {
+    /// Create a LUT by memoizing a function of depth.
+    ///
+    /// The function receives the depth of the fragment, in the range `[0, 1]`,
+    /// and should return the fog factor for that depth.
+    #[doc(alias = "FogLut_FromArray")]
+    pub fn from_fn(mut f: impl FnMut(f32) -> f32) -> Self {
+        // The first half holds the values, the second the difference to the next value
+        let mut data = [0.0f32; 2 * FOG_LUT_STEPS];
+        for i in 0..=FOG_LUT_STEPS {
+            let v = f(i as f32 / FOG_LUT_STEPS as f32);
+            if i < FOG_LUT_STEPS {
+                data[i] = v;
+            }
+            if i > 0 {
+                data[i + FOG_LUT_STEPS - 1] = v - data[i - 1];
+            }
+        }
+
+        let lut = unsafe {
+            let mut lut = MaybeUninit::zeroed();
+            citro3d_sys::FogLut_FromArray(lut.as_mut_ptr(), data.as_ptr());
+            lut.assume_init()
+        };
+        Self(lut)
+    }
+
+    /// Create a LUT for exponential fog, where the fog factor at distance `z` from the
+    /// camera is `exp(-(density * z) ^ gradient)`.
+    ///
+    /// `clip_planes` should match those of the projection used for rendering.
+    #[doc(alias = "FogLut_Exp")]
+    pub fn exponential(density: f32, gradient: f32, clip_planes: ClipPlanes) -> Self {
+        let lut = unsafe {
+            let mut lut = MaybeUninit::zeroed();
+            citro3d_sys::FogLut_Exp(
+                lut.as_mut_ptr(),
+                density,
+                gradient,
+                clip_planes.near,
+                clip_planes.far,
+            );
+            lut.assume_init()
+        };
+        Self(lut)
+    }
+
+    pub(crate) fn as_raw_mut(&mut self) -> &mut citro3d_sys::C3D_FogLut {
+        &mut self.0
+    }
+}
+
+impl PartialEq for FogLut {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.data == other.0.data
+    }
+}
+impl Eq for FogLut {}
+
+impl std::fmt::Debug for FogLut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FogLut").finish_non_exhaustive()
+    }
+}
+
+/// Whether fog (or gas) is applied to fragments.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[doc(alias = "GPU_FOGMODE")]
+pub enum FogMode {
+    /// Fog is disabled.
+    #[doc(alias = "GPU_NO_FOG")]
+    Disabled = ctru_sys::GPU_NO_FOG,
+
+    /// Fog is enabled.
+    #[doc(alias = "GPU_FOG")]
+    Fog = ctru_sys::GPU_FOG,
+
+    /// Gas rendering is enabled. See the [`gas`](crate::gas) module.
+    #[doc(alias = "GPU_GAS")]
+    Gas = ctru_sys::GPU_GAS,
+}
+
+/// How gas density is accumulated, when using [`FogMode::Gas`].
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[doc(alias = "GPU_GASMODE")]
+pub enum GasMode {
+    /// Each fragment adds the same density.
+    #[doc(alias = "GPU_PLAIN_DENSITY")]
+    PlainDensity = ctru_sys::GPU_PLAIN_DENSITY,
+
+    /// Each fragment adds a density based on its depth relative to the
+    /// existing geometry, so gas fades out close to other surfaces.
+    #[doc(alias = "GPU_DEPTH_DENSITY")]
+    DepthDensity = ctru_sys::GPU_DEPTH_DENSITY,
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::{FogLut, FOG_LUT_STEPS};
+    use crate::math::ClipPlanes;
+
+    /// Unpack the fixed-point entries of a LUT into `(value, difference)` pairs.
+    fn unpack(lut: &FogLut) -> Vec<f32> {
+        lut.0
+            .data
+            .iter()
+            .flat_map(|&entry| {
+                let value = (entry >> 13) as f32 / 2048.0;
+                // 13-bit signed difference
+                let diff = (((entry & 0x1FFF) << 19) as i32 >> 19) as f32 / 2048.0;
+                [value, diff]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn from_fn_matches_exponential() {
+        let (density, gradient) = (0.5, 2.0);
+        let (near, far) = (0.1, 10.0);
+
+        let c3d = FogLut::exponential(density, gradient, ClipPlanes { near, far });
+        let rs = FogLut::from_fn(|depth| {
+            let z = far * near / (depth * (far - near) + near);
+            (-(density * z).powf(gradient)).exp()
+        });
+        let (c3d, rs) = (unpack(&c3d), unpack(&rs));
+        assert_eq!(c3d.len(), 2 * FOG_LUT_STEPS);
+        // Allow for a single step of fixed-point rounding
+        assert_abs_diff_eq!(c3d[..], rs[..], epsilon = 1.0 / 2048.0);
+    }
+}
@@ -0,0 +1,97 @@
+//! Gas rendering, the PICA200's volumetric effect for smoke, clouds and the like.
+//!
+//! Gas is drawn in two passes:
+//!
+//! 1. **Accumulation**: after [`Instance::begin_gas_accumulation`](crate::Instance::begin_gas_accumulation),
+//!    gas particles are drawn with [`FogMode::Gas`](crate::fog::FogMode::Gas). Instead of
+//!    producing colors, each fragment adds to the gas density stored in the depth buffer.
+//! 2. **Shading**: the accumulated densities are mapped to colors with a [`GasLut`]
+//!    (bound with [`Instance::bind_gas_lut`](crate::Instance::bind_gas_lut)) and
+//!    shaded using the other gas parameters on [`Instance`](crate::Instance).
+//!
+//! # Example
+//!
+//! ```
+//! # let _runner = test_runner::GdbRunner::default();
+//! # use citro3d::fog::{FogMode, GasMode};
+//! # use citro3d::gas::{GasLut, LutInput};
+//! # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+//! # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+//! instance.render_frame_with(|instance| {
+//!     // draw the scene...
+//!
+//!     instance.begin_gas_accumulation();
+//!     instance.set_fog_gas_mode(FogMode::Gas, GasMode::DepthDensity, false);
+//!     instance.set_gas_delta_z(10.0);
+//!
+//!     // draw gas particles...
+//!
+//!     instance.set_gas_accumulation_max(1.0);
+//!     instance.set_gas_attenuation(0.5);
+//!     instance.set_gas_lut_input(LutInput::Density);
+//!     instance.bind_gas_lut(Some(GasLut::from_fn(|density| {
+//!         let v = (density * 255.0) as u32;
+//!         (v << 16) | (v << 8) | v
+//!     })));
+//!
+//!     // draw a quad covering the gas...
+//!
+//!     instance.set_fog_gas_mode(FogMode::Disabled, GasMode::PlainDensity, false);
+//! });
+//! ```
+
+use std::mem::MaybeUninit;
+
+/// Number of colors a [`GasLut`] is built from.
+const GAS_LUT_STEPS: usize = 9;
+
+/// Lookup table mapping gas density (or light factor, see [`LutInput`]) to a color.
+#[doc(alias = "C3D_GasLut")]
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct GasLut(citro3d_sys::C3D_GasLut);
+
+impl GasLut {
+    /// Create a LUT from 9 colors, in `0xBBGGRR` format, evenly spaced over
+    /// the input range `[0, 1]`. Inputs between them are interpolated.
+    #[doc(alias = "GasLut_FromArray")]
+    pub fn from_colors(colors: [u32; GAS_LUT_STEPS]) -> Self {
+        let lut = unsafe {
+            let mut lut = MaybeUninit::zeroed();
+            citro3d_sys::GasLut_FromArray(lut.as_mut_ptr(), colors.as_ptr());
+            lut.assume_init()
+        };
+        Self(lut)
+    }
+
+    /// Create a LUT by sampling a function returning a color (in `0xBBGGRR` format)
+    /// for inputs in the range `[0, 1]`.
+    pub fn from_fn(mut f: impl FnMut(f32) -> u32) -> Self {
+        let colors = std::array::from_fn(|i| f(i as f32 / (GAS_LUT_STEPS - 1) as f32));
+        Self::from_colors(colors)
+    }
+
+    pub(crate) fn as_raw_mut(&mut self) -> &mut citro3d_sys::C3D_GasLut {
+        &mut self.0
+    }
+}
+
+impl std::fmt::Debug for GasLut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GasLut").finish_non_exhaustive()
+    }
+}
+
+/// The value used to look up the [`GasLut`] during shading.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[doc(alias = "GPU_GASLUTINPUT")]
+pub enum LutInput {
+    /// The accumulated gas density.
+    #[doc(alias = "GPU_GAS_DENSITY")]
+    Density = ctru_sys::GPU_GAS_DENSITY,
+
+    /// The light factor, computed from the planar and view shading parameters.
+    #[doc(alias = "GPU_GAS_LIGHT_FACTOR")]
+    LightFactor = ctru_sys::GPU_GAS_LIGHT_FACTOR,
+}
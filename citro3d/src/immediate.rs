@@ -0,0 +1,53 @@
+//! Immediate-mode drawing, where vertex attributes are sent directly in the
+//! command buffer instead of being read from a buffer in linear memory.
+//!
+//! This is slower than drawing from a [`buffer::Info`](crate::buffer::Info),
+//! but convenient for small amounts of geometry like debug overlays.
+//! See [`Instance::draw_immediate`](crate::Instance::draw_immediate).
+
+use crate::math::FVec4;
+
+/// Handle for submitting vertices during an immediate-mode draw.
+///
+/// Each vertex is made up of one value per attribute in the currently bound
+/// [`attrib::Info`](crate::attrib::Info), sent in the same order the attributes
+/// were added to it.
+#[derive(Debug)]
+pub struct Immediate {
+    attr_count: usize,
+}
+
+impl Immediate {
+    pub(crate) fn new(attr_count: usize) -> Self {
+        Self { attr_count }
+    }
+
+    /// Send the value of a single attribute of the current vertex.
+    ///
+    /// Prefer [`Self::vertex`] where possible, since it checks the number of
+    /// attributes sent matches the bound [`attrib::Info`](crate::attrib::Info).
+    #[doc(alias = "C3D_ImmSendAttrib")]
+    pub fn attrib(&mut self, x: f32, y: f32, z: f32, w: f32) {
+        unsafe {
+            citro3d_sys::C3D_ImmSendAttrib(x, y, z, w);
+        }
+    }
+
+    /// Send a whole vertex, i.e. the value for each attribute.
+    ///
+    /// # Panics
+    ///
+    /// If `attribs` doesn't have exactly one value per attribute in the bound
+    /// [`attrib::Info`](crate::attrib::Info).
+    pub fn vertex(&mut self, attribs: &[FVec4]) {
+        assert_eq!(
+            attribs.len(),
+            self.attr_count,
+            "immediate vertex must have one value per bound attribute"
+        );
+
+        for a in attribs {
+            self.attrib(a.x(), a.y(), a.z(), a.w());
+        }
+    }
+}
@@ -1,3 +1,4 @@
+#![feature(allocator_api)]
 #![feature(new_uninit)]
 #![feature(custom_test_frameworks)]
 #![test_runner(test_runner::run_gdb)]
@@ -20,9 +21,14 @@
 pub mod attrib;
 pub mod buffer;
 pub mod error;
+pub mod fog;
+pub mod gas;
+pub mod immediate;
 pub mod light;
+pub mod limits;
 pub mod material;
 pub mod math;
+pub mod proctex;
 pub mod render;
 pub mod shader;
 pub mod texenv;
@@ -31,12 +37,14 @@ pub mod uniform;
 mod util;
 
 use std::fmt;
+use std::marker::PhantomData;
 use std::pin::Pin;
 use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 
+use ctru::services::gfx::Gfx;
 pub use error::{Error, Result};
 use static_assertions::assert_impl_all;
-use util::is_linear_ptr;
 
 use self::texenv::TexEnv;
 use self::uniform::Uniform;
@@ -44,93 +52,1336 @@ use self::uniform::Uniform;
 pub mod macros {
     //! Helper macros for working with shaders.
     pub use citro3d_macros::*;
+
+    /// Compiles the given PICA200 shader like [`include_shader!`], and wraps
+    /// the resulting bytes in a [`shader::EmbeddedLibrary`](crate::shader::EmbeddedLibrary),
+    /// which parses them into a [`shader::Library`](crate::shader::Library)
+    /// on first use. Arguments are the same as for [`include_shader!`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// use citro3d::macros::include_shader_library;
+    /// use citro3d::shader::EmbeddedLibrary;
+    ///
+    /// static SHADER: EmbeddedLibrary = include_shader_library!("../examples/assets/vshader.pica");
+    ///
+    /// assert_eq!(SHADER.library().len(), 1);
+    /// ```
+    #[macro_export]
+    macro_rules! include_shader_library {
+        ($($args:tt)*) => {
+            $crate::shader::EmbeddedLibrary::new($crate::macros::include_shader!($($args)*))
+        };
+    }
+
+    pub use crate::include_shader_library;
 }
 
 /// The single instance for using `citro3d`. This is the base type that an application
 /// should instantiate to use this library.
+///
+/// The instance borrows the [`Gfx`] it was created with, so graphics must be
+/// initialized first and stay initialized for as long as the instance exists.
 #[non_exhaustive]
 #[must_use]
-pub struct Instance {
+pub struct Instance<'gfx> {
     texenvs: [OnceLock<TexEnv>; texenv::TEXENV_COUNT],
     /// The shader in use, we keep it at the rust level because the C API needs it to stay valid
     /// (at a fixed address) once bound
     shader: Option<Pin<Arc<shader::Program>>>,
     light_env: Pin<Box<light::LightEnv>>,
+    /// The bound fog LUT, which citro3d keeps a pointer to
+    fog_lut: Option<Box<fog::FogLut>>,
+    /// The bound gas LUT, which citro3d keeps a pointer to
+    gas_lut: Option<Box<gas::GasLut>>,
+    /// The bound procedural texture state, which citro3d keeps pointers to
+    proctex: proctex::Bindings,
+    /// Number of draw calls since the start of the frame
+    draw_calls: usize,
+    /// Number of frames begun, used to clear render targets once per frame
+    frame: u64,
+    /// Whether `C3D_FrameBegin` already succeeded while polling the GPU, so the
+    /// next frame must not begin again
+    frame_begun: bool,
+    /// Size of the command buffer passed to `C3D_Init`, in bytes
+    cmdbuf_size: usize,
+    /// The state last set through this instance, since citro3d can't report it
+    render_state: render::RenderState,
+    /// States saved by `push_state`
+    state_stack: Vec<render::RenderState>,
+    /// The depth map as `(w_buffer, z_scale, z_offset)`, since the polygon
+    /// offset is applied through the same call
+    depth_map: (bool, f32, f32),
+    /// The polygon offset added to the depth map's offset
+    polygon_offset: f32,
+    /// The user clip plane, which citro3d doesn't manage and so must be
+    /// written again at the start of every frame
+    clip_plane: Option<math::FVec4>,
+    /// The hook registered with `C3D_FrameEndHook`, which citro3d keeps a pointer to
+    frame_end_hook: Option<Box<FrameEndHook>>,
+    /// Ties the instance to the lifetime of the `Gfx` it renders with. This
+    /// is a function pointer so the instance stays `Send` and `Sync`, since
+    /// only the borrow matters, not access to the `Gfx`.
+    _gfx: PhantomData<fn() -> &'gfx Gfx>,
 }
 
-impl fmt::Debug for Instance {
+/// A closure run by citro3d at the end of each frame.
+struct FrameEndHook(Box<dyn FnMut() + Send>);
+
+// SAFETY: the closure is only ever called through a unique reference, from
+// within `C3D_FrameEnd` while the owning `Instance` is mutably borrowed.
+unsafe impl Sync for FrameEndHook {}
+
+unsafe extern "C" fn frame_end_trampoline(param: *mut std::ffi::c_void) {
+    let hook = unsafe { &mut *param.cast::<FrameEndHook>() };
+    (hook.0)();
+}
+
+impl fmt::Debug for Instance<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Instance").finish_non_exhaustive()
     }
-}
+}
+
+impl<'gfx> Instance<'gfx> {
+    /// The command buffer size used by [`Self::new`], in bytes.
+    #[doc(alias = "C3D_DEFAULT_CMDBUF_SIZE")]
+    pub const DEFAULT_CMDBUF_SIZE: usize = citro3d_sys::C3D_DEFAULT_CMDBUF_SIZE as usize;
+
+    /// The alignment required for command buffer sizes, in bytes. The GPU
+    /// processes command lists in blocks of this size.
+    pub const CMDBUF_ALIGNMENT: usize = 16;
+
+    /// Initialize the default `citro3d` instance.
+    ///
+    /// `citro3d` renders to the framebuffers set up by [`Gfx`], and relies on the
+    /// GSP service it initializes, so the instance can only be created once
+    /// graphics are initialized. The instance borrows `gfx`, so it can't
+    /// outlive it either.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `citro3d` cannot be initialized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// use ctru::services::gfx::Gfx;
+    ///
+    /// let gfx = Gfx::new().unwrap();
+    /// let instance = citro3d::Instance::new(&gfx).unwrap();
+    /// ```
+    pub fn new(gfx: &'gfx Gfx) -> Result<Self> {
+        Self::with_cmdbuf_size(gfx, Self::DEFAULT_CMDBUF_SIZE)
+    }
+
+    /// Initialize the instance with a specified command buffer size, in bytes.
+    ///
+    /// The default size used by [`Self::new`] is
+    /// [`DEFAULT_CMDBUF_SIZE`](Self::DEFAULT_CMDBUF_SIZE). The command buffer
+    /// can't be resized after initialization, so use [`Self::cmdbuf_usage`] to
+    /// find out whether a frame needs a bigger one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidCmdBufSize`] if `size` is not a non-zero multiple
+    /// of [`CMDBUF_ALIGNMENT`](Self::CMDBUF_ALIGNMENT), or
+    /// [`Error::InitFailed`] if `citro3d` cannot be initialized, e.g. because
+    /// there isn't enough linear memory for the command buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::{Error, Instance};
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// assert!(matches!(
+    ///     Instance::with_cmdbuf_size(&gfx, 1000),
+    ///     Err(Error::InvalidCmdBufSize { size: 1000 })
+    /// ));
+    ///
+    /// let instance = Instance::with_cmdbuf_size(&gfx, 2 * Instance::DEFAULT_CMDBUF_SIZE).unwrap();
+    /// assert_eq!(instance.cmdbuf_size(), 2 * Instance::DEFAULT_CMDBUF_SIZE);
+    /// ```
+    #[doc(alias = "C3D_Init")]
+    pub fn with_cmdbuf_size(_gfx: &'gfx Gfx, size: usize) -> Result<Self> {
+        if size == 0 || size % Self::CMDBUF_ALIGNMENT != 0 {
+            return Err(Error::InvalidCmdBufSize { size });
+        }
+
+        if unsafe { citro3d_sys::C3D_Init(size) } {
+            let mut light_env = Box::pin(light::LightEnv::new());
+            unsafe {
+                // setup the light env slot, since this is a pointer copy it will stick around even with we swap
+                // out light_env later
+                citro3d_sys::C3D_LightEnvBind(light_env.as_mut().as_raw_mut());
+            }
+            Ok(Self {
+                texenvs: std::array::from_fn(|_| OnceLock::new()),
+                shader: None,
+                light_env,
+                fog_lut: None,
+                gas_lut: None,
+                proctex: Default::default(),
+                draw_calls: 0,
+                frame: 0,
+                frame_begun: false,
+                cmdbuf_size: size,
+                render_state: Default::default(),
+                state_stack: Vec::new(),
+                depth_map: (false, -1.0, 0.0),
+                polygon_offset: 0.0,
+                clip_plane: None,
+                frame_end_hook: None,
+                _gfx: PhantomData,
+            })
+        } else {
+            Err(Error::InitFailed { cmdbuf_size: size })
+        }
+    }
+
+    /// Select the given render target for drawing the frame.
+    ///
+    /// If the target has a clear configured with [`render::Target::set_clear`],
+    /// it is cleared the first time it is selected in each frame.
+    ///
+    /// Selecting a target resets the [viewport](Self::set_viewport) to cover
+    /// the whole target and disables the [scissor test](Self::set_scissor).
+    /// All other state (e.g. culling, blending, depth and stencil tests,
+    /// [`TexEnv`] stages, bound textures, uniforms and shader program) is
+    /// global and carries over between targets and frames. See
+    /// [`Self::push_state`] to save and restore the viewport, scissor and
+    /// culling around a target switch.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the given target cannot be used for drawing.
+    #[doc(alias = "C3D_FrameDrawOn")]
+    pub fn select_render_target(&mut self, target: &render::Target<'_>) -> Result<()> {
+        target.clear_on_select(self.frame);
+        if unsafe { citro3d_sys::C3D_FrameDrawOn(target.as_raw()) } {
+            let (width, height) = target.dimensions();
+            self.render_state.viewport = Some([0, 0, width, height]);
+            self.render_state.scissor = None;
+            Ok(())
+        } else {
+            Err(Error::InvalidRenderTarget)
+        }
+    }
+
+    /// Get the current viewport, scissor and culling state, as set through
+    /// this instance.
+    pub fn render_state(&self) -> render::RenderState {
+        self.render_state
+    }
+
+    /// Restore a viewport, scissor and culling state previously saved with
+    /// [`Self::render_state`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::CullMode;
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// let saved = instance.render_state();
+    ///
+    /// instance.set_cull_face(CullMode::None);
+    /// instance.set_viewport(0, 0, 120, 200);
+    ///
+    /// instance.set_render_state(&saved);
+    /// assert_eq!(instance.render_state(), saved);
+    /// ```
+    pub fn set_render_state(&mut self, state: &render::RenderState) {
+        // The viewport must be set first, since setting it disables the scissor
+        if let Some([x, y, width, height]) = state.viewport {
+            self.set_viewport(x, y, width, height);
+        }
+        match state.scissor {
+            Some((mode, [left, top, right, bottom])) => {
+                // The rectangle was already validated when it was first set
+                let _ = self.set_scissor(mode, left, top, right, bottom);
+            }
+            None => {
+                let _ = self.set_scissor(render::ScissorMode::Disable, 0, 0, 0, 0);
+            }
+        }
+        self.set_cull_face(state.cull_mode);
+        // Also records a `None` viewport, which can't be applied
+        self.render_state = *state;
+    }
+
+    /// Save the current viewport, scissor and culling state, to be restored by
+    /// the matching [`Self::pop_state`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::{CullMode, ScissorMode};
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// instance.push_state();
+    /// instance.set_cull_face(CullMode::None);
+    /// instance.set_scissor(ScissorMode::Normal, 0, 0, 120, 200).unwrap();
+    /// // draw the UI...
+    /// instance.pop_state();
+    ///
+    /// assert_eq!(instance.render_state().cull_mode, CullMode::BackCcw);
+    /// assert_eq!(instance.render_state().scissor, None);
+    /// ```
+    pub fn push_state(&mut self) {
+        self.state_stack.push(self.render_state);
+    }
+
+    /// Restore the state saved by the last call to [`Self::push_state`], and
+    /// return it. Returns `None` without changing anything if no state was saved.
+    pub fn pop_state(&mut self) -> Option<render::RenderState> {
+        let state = self.state_stack.pop()?;
+        self.set_render_state(&state);
+        Some(state)
+    }
+
+    /// Set the viewport, i.e. the rectangle of the render target that normalized
+    /// device coordinates are mapped to. This can be used to render to only part
+    /// of the target, e.g. for a lower internal resolution or letterboxing.
+    ///
+    /// Like the [scissor](Self::set_scissor), coordinates are in framebuffer space,
+    /// which is rotated 90° relative to the physical screen. `x` and `width` are
+    /// along the physical screen's *vertical* axis (`0..240` on both screens), and
+    /// `y` and `height` along its *horizontal* axis (`0..400` on the top screen,
+    /// `0..320` on the bottom).
+    ///
+    /// Selecting a render target with [`Self::select_render_target`] resets the
+    /// viewport to cover the whole target, so it must be set again afterwards.
+    /// Setting the viewport also disables the scissor test.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// // Draw to the middle 200 pixels of the top screen
+    /// instance.set_viewport(0, 100, 240, 200);
+    /// ```
+    #[doc(alias = "C3D_SetViewport")]
+    pub fn set_viewport(&mut self, x: u32, y: u32, width: u32, height: u32) {
+        unsafe {
+            citro3d_sys::C3D_SetViewport(x, y, width, height);
+        }
+        self.render_state.viewport = Some([x, y, width, height]);
+        self.render_state.scissor = None;
+    }
+
+    /// Configure the scissor test, which restricts drawing to (or outside of)
+    /// the given rectangle.
+    ///
+    /// Coordinates are in framebuffer space, which is rotated 90° relative to the
+    /// physical screen (see [`math::ScreenOrientation`]). This means that
+    /// `left`/`right` are positions along the physical screen's *vertical* axis
+    /// (`0..240` on both screens), and `top`/`bottom` are positions along its
+    /// *horizontal* axis (`0..400` on the top screen, `0..320` on the bottom).
+    ///
+    /// The scissor is reset whenever a new render target is
+    /// [selected](Self::select_render_target), so it must be set again afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidSize`] if `right < left` or `bottom < top`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::ScissorMode;
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// instance.set_scissor(ScissorMode::Normal, 0, 0, 120, 200).unwrap();
+    /// assert!(instance.set_scissor(ScissorMode::Normal, 120, 0, 0, 200).is_err());
+    /// ```
+    #[doc(alias = "C3D_SetScissor")]
+    pub fn set_scissor(
+        &mut self,
+        mode: render::ScissorMode,
+        left: u32,
+        top: u32,
+        right: u32,
+        bottom: u32,
+    ) -> Result<()> {
+        if right < left || bottom < top {
+            return Err(Error::InvalidSize);
+        }
+
+        unsafe {
+            citro3d_sys::C3D_SetScissor(
+                mode as ctru_sys::GPU_SCISSORMODE,
+                left,
+                top,
+                right,
+                bottom,
+            );
+        }
+        self.render_state.scissor = match mode {
+            render::ScissorMode::Disable => None,
+            _ => Some((mode, [left, top, right, bottom])),
+        };
+
+        Ok(())
+    }
+
+    /// Configure the depth test and which buffer components are written to
+    /// by subsequent draw calls.
+    ///
+    /// `write_mask` applies even when the test is disabled. To draw translucent
+    /// geometry that is correctly occluded by (but does not occlude) other geometry,
+    /// keep the test enabled but leave [`WriteMask::DEPTH`](render::WriteMask::DEPTH)
+    /// out of the mask.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::{DepthFunc, WriteMask};
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// // Test depth for translucent surfaces without writing to the depth buffer
+    /// instance.set_depth_test(true, DepthFunc::Greater, WriteMask::COLOR);
+    /// ```
+    #[doc(alias = "C3D_DepthTest")]
+    pub fn set_depth_test(
+        &mut self,
+        enabled: bool,
+        function: render::DepthFunc,
+        write_mask: render::WriteMask,
+    ) {
+        unsafe {
+            citro3d_sys::C3D_DepthTest(
+                enabled,
+                function as ctru_sys::GPU_TESTFUNC,
+                write_mask.bits(),
+            );
+        }
+    }
+
+    /// Configure how clip-space depth is mapped to the value stored in the
+    /// depth buffer: `depth = z * z_scale + z_offset`. If `w_buffer` is `true`,
+    /// the depth is additionally multiplied by W before being stored, giving
+    /// linear precision over the view distance instead of the usual
+    /// non-linear Z distribution.
+    ///
+    /// The default mapping is `(false, -1.0, 0.0)`. This suits the projection
+    /// matrices from [`math::Projection`], which (like all citro3d projections)
+    /// map clip-space depth to `[-1, 0]`, with the near plane at `-1`. The depth
+    /// buffer then holds `1.0` at the near plane and `0.0` at the far plane,
+    /// which is why [`DepthFunc::Greater`](render::DepthFunc::Greater) is the usual depth test.
+    ///
+    /// Projections built for other depth ranges need a different mapping to keep
+    /// the same depth buffer layout:
+    ///
+    /// * `[-1, 1]` (OpenGL-style): `z_scale = -0.5`, `z_offset = 0.5`
+    /// * `[0, 1]` (Direct3D-style): `z_scale = -1.0`, `z_offset = 1.0`
+    ///
+    /// Any [polygon offset](Self::set_polygon_offset) is added to `z_offset`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// // Use with a projection matrix from e.g. `glam::Mat4::perspective_rh_gl`
+    /// instance.set_depth_map(false, -0.5, 0.5);
+    /// ```
+    #[doc(alias = "C3D_DepthMap")]
+    pub fn set_depth_map(&mut self, w_buffer: bool, z_scale: f32, z_offset: f32) {
+        self.depth_map = (w_buffer, z_scale, z_offset);
+        self.apply_depth_map();
+    }
+
+    /// Offset the depth of everything drawn afterwards by `units` steps of a
+    /// 24-bit depth buffer, e.g. to keep decals from z-fighting with the
+    /// surface they are drawn on.
+    ///
+    /// The PICA has no dedicated polygon offset, so this is added to the offset
+    /// of the [depth map](Self::set_depth_map), which is kept in place. The
+    /// PICA also has no slope-scaled offset, so unlike with OpenGL's
+    /// `glPolygonOffset`, **`factor` is ignored**: it is only accepted for
+    /// parity with `glPolygonOffset`. The offset is constant across a polygon,
+    /// so surfaces at a steep angle to the camera may need a larger `units`.
+    ///
+    /// With the default depth map, the depth buffer holds larger values closer
+    /// to the camera, so a **positive** offset pulls geometry towards the camera
+    /// (the opposite sign of OpenGL). Remember to reset the offset with
+    /// [`Self::clear_polygon_offset`] after drawing, since it applies to all
+    /// later draws.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// instance.set_polygon_offset(0.0, 16.0);
+    /// // draw decals...
+    /// instance.clear_polygon_offset();
+    /// # assert_eq!(instance.polygon_offset(), 0.0);
+    /// ```
+    #[doc(alias = "C3D_DepthMap")]
+    #[doc(alias = "glPolygonOffset")]
+    pub fn set_polygon_offset(&mut self, _factor: f32, units: f32) {
+        self.polygon_offset = units;
+        self.apply_depth_map();
+    }
+
+    /// Reset the offset set with [`Self::set_polygon_offset`] to zero.
+    #[doc(alias = "C3D_DepthMap")]
+    pub fn clear_polygon_offset(&mut self) {
+        self.set_polygon_offset(0.0, 0.0);
+    }
+
+    /// The offset set with [`Self::set_polygon_offset`], in depth buffer steps.
+    pub fn polygon_offset(&self) -> f32 {
+        self.polygon_offset
+    }
+
+    /// Enable the user clip plane, discarding the parts of primitives for which
+    /// `dot(plane, position) < 0`, e.g. to only draw what is above the water
+    /// surface in a planar reflection pass.
+    ///
+    /// The plane is given in clip space, i.e. it is compared against the
+    /// position output by the vertex (or geometry) shader. A plane `p` in view
+    /// space can be transformed to clip space using the inverse transpose of the
+    /// projection matrix. Clipping is done by the fixed-function pipeline, so the
+    /// shader doesn't need to output anything besides the position.
+    ///
+    /// The plane stays enabled across frames until [`Self::disable_clip_plane`]
+    /// is called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::FVec4;
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// // Only keep geometry with a positive clip-space y coordinate
+    /// instance.set_clip_plane(FVec4::new(0.0, 1.0, 0.0, 0.0));
+    /// // draw the reflection pass...
+    /// instance.disable_clip_plane();
+    /// # assert_eq!(instance.clip_plane(), None);
+    /// ```
+    #[doc(alias = "GPUREG_FRAG_OP_CLIP")]
+    pub fn set_clip_plane(&mut self, plane: math::FVec4) {
+        self.clip_plane = Some(plane);
+        self.write_clip_plane();
+    }
+
+    /// Disable the user clip plane set with [`Self::set_clip_plane`].
+    #[doc(alias = "GPUREG_FRAG_OP_CLIP")]
+    pub fn disable_clip_plane(&mut self) {
+        self.clip_plane = None;
+        self.write_clip_plane();
+    }
+
+    /// The user clip plane set with [`Self::set_clip_plane`], if enabled.
+    pub fn clip_plane(&self) -> Option<math::FVec4> {
+        self.clip_plane
+    }
+
+    fn write_clip_plane(&self) {
+        util::write_gpu_regs(
+            ctru_sys::GPUREG_FRAG_OP_CLIP,
+            &[u32::from(self.clip_plane.is_some())],
+        );
+        if let Some(plane) = self.clip_plane {
+            util::write_gpu_regs(
+                ctru_sys::GPUREG_FRAG_OP_CLIP_DATA0,
+                &[plane.x(), plane.y(), plane.z(), plane.w()].map(util::f32_to_f24),
+            );
+        }
+    }
+
+    fn apply_depth_map(&mut self) {
+        /// The size of one step of a 24-bit depth buffer
+        const DEPTH_UNIT: f32 = 1.0 / 0xFF_FFFF as f32;
+
+        let (w_buffer, z_scale, z_offset) = self.depth_map;
+        unsafe {
+            citro3d_sys::C3D_DepthMap(
+                !w_buffer,
+                z_scale,
+                z_offset + self.polygon_offset * DEPTH_UNIT,
+            );
+        }
+    }
+
+    /// Configure the early depth test, which discards fragments before they are
+    /// processed by the rest of the fragment pipeline. This compares the incoming
+    /// depth against a separate early depth buffer, with `reference` (a 24-bit depth
+    /// value) being the value it is cleared to.
+    ///
+    /// The early depth test should be disabled for draws that use the alpha test or
+    /// otherwise discard fragments, or that don't write to the depth buffer, since the
+    /// early depth buffer would no longer match the regular one, causing artifacts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::EarlyDepthFunc;
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// instance.set_early_depth_test(true, EarlyDepthFunc::Greater, 0);
+    ///
+    /// // draw opaque geometry...
+    ///
+    /// instance.set_early_depth_test(false, EarlyDepthFunc::Greater, 0);
+    ///
+    /// // draw alpha-tested geometry...
+    /// ```
+    ///
+    /// The function can also be derived from the one used for the regular
+    /// depth test:
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::{EarlyDepthFunc, TestFunc, WriteMask};
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// let func = TestFunc::Greater;
+    /// instance.set_depth_test(true, func, WriteMask::ALL);
+    /// instance.set_early_depth_test(true, EarlyDepthFunc::try_from(func).unwrap(), 0);
+    /// ```
+    #[doc(alias = "C3D_EarlyDepthTest")]
+    pub fn set_early_depth_test(
+        &mut self,
+        enabled: bool,
+        func: render::EarlyDepthFunc,
+        reference: u32,
+    ) {
+        unsafe {
+            citro3d_sys::C3D_EarlyDepthTest(
+                enabled,
+                func as ctru_sys::GPU_EARLYDEPTHFUNC,
+                reference,
+            );
+        }
+    }
+
+    /// Configure alpha blending for subsequent draw calls. The color (RGB) and
+    /// alpha components of each fragment are blended separately, using their own
+    /// [`BlendEquation`](render::BlendEquation) and source/destination
+    /// [`BlendFactor`](render::BlendFactor)s.
+    ///
+    /// Blending and color logic operations are mutually exclusive on the hardware,
+    /// so enabling blending disables any color logic operation.
+    #[doc(alias = "C3D_AlphaBlend")]
+    pub fn set_blend(
+        &mut self,
+        color_eq: render::BlendEquation,
+        alpha_eq: render::BlendEquation,
+        color_src: render::BlendFactor,
+        color_dst: render::BlendFactor,
+        alpha_src: render::BlendFactor,
+        alpha_dst: render::BlendFactor,
+    ) {
+        unsafe {
+            citro3d_sys::C3D_AlphaBlend(
+                color_eq as ctru_sys::GPU_BLENDEQUATION,
+                alpha_eq as ctru_sys::GPU_BLENDEQUATION,
+                color_src as ctru_sys::GPU_BLENDFACTOR,
+                color_dst as ctru_sys::GPU_BLENDFACTOR,
+                alpha_src as ctru_sys::GPU_BLENDFACTOR,
+                alpha_dst as ctru_sys::GPU_BLENDFACTOR,
+            );
+        }
+    }
+
+    /// Configure "standard" alpha blending, where each fragment's color is
+    /// blended with the existing color using its alpha value
+    /// (i.e. `src * src_alpha + dst * (1 - src_alpha)`).
+    ///
+    /// As with [`Self::set_blend`], this disables any color logic operation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// instance.set_blend_standard_alpha();
+    /// ```
+    #[doc(alias = "C3D_AlphaBlend")]
+    pub fn set_blend_standard_alpha(&mut self) {
+        use render::{BlendEquation, BlendFactor};
+
+        self.set_blend(
+            BlendEquation::Add,
+            BlendEquation::Add,
+            BlendFactor::SrcAlpha,
+            BlendFactor::OneMinusSrcAlpha,
+            BlendFactor::SrcAlpha,
+            BlendFactor::OneMinusSrcAlpha,
+        );
+    }
+
+    /// Combine each fragment's color with the existing color using a bitwise
+    /// [`LogicOp`](render::LogicOp) instead of blending.
+    ///
+    /// Blending and color logic operations are mutually exclusive on the hardware,
+    /// so this disables blending until [`Self::set_blend`] is called again.
+    /// Use [`LogicOp::Copy`](render::LogicOp::Copy) to draw without either.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::LogicOp;
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// // Draw an XOR cursor
+    /// instance.set_color_logic_op(LogicOp::Xor);
+    ///
+    /// // Back to regular drawing
+    /// instance.set_color_logic_op(LogicOp::Copy);
+    /// ```
+    #[doc(alias = "C3D_ColorLogicOp")]
+    pub fn set_color_logic_op(&mut self, op: render::LogicOp) {
+        unsafe {
+            citro3d_sys::C3D_ColorLogicOp(op as ctru_sys::GPU_LOGICOP);
+        }
+    }
+
+    /// Configure which faces of triangles are culled by subsequent draw calls.
+    ///
+    /// Counter-clockwise triangles (as seen on screen, after projection) are
+    /// considered front-facing, the same as OpenGL's default. However, unlike
+    /// OpenGL, `citro3d` enables culling by default: [`CullMode::BackCcw`](render::CullMode::BackCcw)
+    /// is already set when the [`Instance`] is created, so geometry wound the
+    /// "wrong" way will not be visible unless culling is disabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::CullMode;
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// instance.set_cull_face(CullMode::None);
+    /// ```
+    #[doc(alias = "C3D_CullFace")]
+    pub fn set_cull_face(&mut self, mode: render::CullMode) {
+        unsafe {
+            citro3d_sys::C3D_CullFace(mode as ctru_sys::GPU_CULLMODE);
+        }
+        self.render_state.cull_mode = mode;
+    }
+
+    /// Configure the alpha test, which discards fragments whose alpha value
+    /// does not pass the comparison `<fragment alpha> <func> <reference>`.
+    /// The alpha values are compared as 8-bit values, so a `reference` of `255`
+    /// corresponds to a fully opaque fragment.
+    ///
+    /// See also [`Self::disable_alpha_test`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::TestFunc;
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// // Discard fully transparent fragments
+    /// instance.set_alpha_test(true, TestFunc::Greater, 0);
+    /// ```
+    #[doc(alias = "C3D_AlphaTest")]
+    pub fn set_alpha_test(&mut self, enabled: bool, func: render::TestFunc, reference: u8) {
+        unsafe {
+            citro3d_sys::C3D_AlphaTest(enabled, func as ctru_sys::GPU_TESTFUNC, reference.into());
+        }
+    }
+
+    /// Disable the alpha test, so that no fragments are discarded based on
+    /// their alpha value.
+    #[doc(alias = "C3D_AlphaTest")]
+    pub fn disable_alpha_test(&mut self) {
+        self.set_alpha_test(false, render::TestFunc::Always, 0);
+    }
+
+    /// Configure the stencil test. The stencil buffer is only available for render
+    /// targets created with [`DepthFormat::Depth24Stencil8`](render::DepthFormat::Depth24Stencil8).
+    ///
+    /// # Parameters
+    ///
+    /// * `enabled`: whether to perform the stencil test at all.
+    /// * `func`: the comparison to perform. The test passes if
+    ///   `(reference & input_mask) <func> (stencil & input_mask)`.
+    /// * `reference`: the value to compare against, which is also the value written
+    ///   by [`StencilOp::Replace`](render::StencilOp::Replace).
+    /// * `input_mask`: the bits of both `reference` and the existing stencil value
+    ///   that are used by the comparison. Bits that are `0` are ignored.
+    /// * `write_mask`: the bits of the stencil buffer that may be modified by the
+    ///   operations set with [`Self::set_stencil_op`]. Bits that are `0` are
+    ///   left untouched.
+    ///
+    /// The two masks are independent: `input_mask` only affects the test, and
+    /// `write_mask` only affects updates to the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::{StencilOp, TestFunc};
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// // Mark every drawn pixel with a stencil value of 1
+    /// instance.set_stencil_test(true, TestFunc::Always, 1, 0xFF, 0xFF);
+    /// instance.set_stencil_op(StencilOp::Keep, StencilOp::Keep, StencilOp::Replace);
+    /// ```
+    #[doc(alias = "C3D_StencilTest")]
+    pub fn set_stencil_test(
+        &mut self,
+        enabled: bool,
+        func: render::TestFunc,
+        reference: u8,
+        input_mask: u8,
+        write_mask: u8,
+    ) {
+        unsafe {
+            citro3d_sys::C3D_StencilTest(
+                enabled,
+                func as ctru_sys::GPU_TESTFUNC,
+                reference.into(),
+                input_mask.into(),
+                write_mask.into(),
+            );
+        }
+    }
+
+    /// Configure how the stencil buffer is updated for each fragment.
+    ///
+    /// # Parameters
+    ///
+    /// * `stencil_fail`: the operation to apply if the stencil test fails.
+    /// * `depth_fail`: the operation to apply if the stencil test passes, but
+    ///   the depth test fails.
+    /// * `pass`: the operation to apply if both the stencil and depth tests pass.
+    #[doc(alias = "C3D_StencilOp")]
+    pub fn set_stencil_op(
+        &mut self,
+        stencil_fail: render::StencilOp,
+        depth_fail: render::StencilOp,
+        pass: render::StencilOp,
+    ) {
+        unsafe {
+            citro3d_sys::C3D_StencilOp(
+                stencil_fail as ctru_sys::GPU_STENCILOP,
+                depth_fail as ctru_sys::GPU_STENCILOP,
+                pass as ctru_sys::GPU_STENCILOP,
+            );
+        }
+    }
+
+    /// Enable or disable fog. When enabled, the LUT bound with
+    /// [`Self::bind_fog_lut`] decides how much fog is applied at each depth.
+    ///
+    /// If `z_flip` is `true`, the depth used to index the LUT is flipped
+    /// (i.e. `1.0 - depth`), for use with reversed depth buffers.
+    ///
+    /// See the [`fog`] module for an example.
+    #[doc(alias = "C3D_FogGasMode")]
+    pub fn set_fog_mode(&mut self, mode: fog::FogMode, z_flip: bool) {
+        self.set_fog_gas_mode(mode, fog::GasMode::PlainDensity, z_flip);
+    }
+
+    /// Set the fog mode like [`Self::set_fog_mode`], along with how gas density
+    /// is accumulated when `fog_mode` is [`FogMode::Gas`](fog::FogMode::Gas).
+    ///
+    /// See the [`gas`] module for an example.
+    #[doc(alias = "C3D_FogGasMode")]
+    pub fn set_fog_gas_mode(
+        &mut self,
+        fog_mode: fog::FogMode,
+        gas_mode: fog::GasMode,
+        z_flip: bool,
+    ) {
+        unsafe {
+            citro3d_sys::C3D_FogGasMode(
+                fog_mode as ctru_sys::GPU_FOGMODE,
+                gas_mode as ctru_sys::GPU_GASMODE,
+                z_flip,
+            );
+        }
+    }
+
+    /// Set the color fragments are blended towards by fog, in `0xBBGGRR` format.
+    #[doc(alias = "C3D_FogColor")]
+    pub fn set_fog_color(&mut self, color: u32) {
+        unsafe {
+            citro3d_sys::C3D_FogColor(color);
+        }
+    }
+
+    /// Bind the LUT used when fog is enabled, or unbind it with `None`.
+    ///
+    /// The LUT stays bound when fog is disabled with [`Self::set_fog_mode`].
+    #[doc(alias = "C3D_FogLutBind")]
+    pub fn bind_fog_lut(&mut self, lut: Option<fog::FogLut>) {
+        self.fog_lut = lut.map(Box::new);
+
+        let raw = match &mut self.fog_lut {
+            Some(lut) => lut.as_raw_mut() as *mut _,
+            None => std::ptr::null_mut(),
+        };
+        // Safety: citro3d keeps this pointer around, but the LUT lives in `self.fog_lut`
+        // on the heap until it is replaced, at which point it is rebound here.
+        unsafe {
+            citro3d_sys::C3D_FogLutBind(raw);
+        }
+    }
+
+    /// Bind procedural texture parameters, or unbind them with `None`. The
+    /// procedural texture uses the texture coordinates of texture unit
+    /// `texcoord_id` (0, 1 or 2).
+    ///
+    /// See the [`proctex`] module for an example.
+    ///
+    /// # Panics
+    ///
+    /// If `texcoord_id` is greater than 2.
+    #[doc(alias = "C3D_ProcTexBind")]
+    pub fn bind_proctex(&mut self, texcoord_id: u8, proctex: Option<proctex::ProcTex>) {
+        assert!(texcoord_id <= 2, "invalid procedural texture coordinate id");
+
+        self.proctex.proctex = proctex.map(Box::new);
+        let raw = match &mut self.proctex.proctex {
+            Some(pt) => pt.as_raw_mut() as *mut _,
+            None => std::ptr::null_mut(),
+        };
+        // Safety: the parameters live in `self.proctex` until they are replaced and rebound
+        unsafe {
+            citro3d_sys::C3D_ProcTexBind(texcoord_id.into(), raw);
+        }
+    }
+
+    /// Bind one of the procedural texture mapping LUTs, or unbind it with `None`.
+    #[doc(alias = "C3D_ProcTexLutBind")]
+    pub fn bind_proctex_lut(&mut self, id: proctex::LutId, lut: Option<proctex::Lut>) {
+        let slot = &mut self.proctex.luts[id.index()];
+        *slot = lut.map(Box::new);
+        let raw = match slot {
+            Some(lut) => lut.as_raw_mut() as *mut _,
+            None => std::ptr::null_mut(),
+        };
+        // Safety: the LUT lives in `self.proctex` until it is replaced and rebound
+        unsafe {
+            citro3d_sys::C3D_ProcTexLutBind(id as ctru_sys::GPU_PROCTEX_LUTID, raw);
+        }
+    }
+
+    /// Bind the procedural texture color LUT, or unbind it with `None`.
+    #[doc(alias = "C3D_ProcTexColorLutBind")]
+    pub fn bind_proctex_color_lut(&mut self, lut: Option<proctex::ColorLut>) {
+        self.proctex.color_lut = lut.map(Box::new);
+        let raw = match &mut self.proctex.color_lut {
+            Some(lut) => lut.as_raw_mut() as *mut _,
+            None => std::ptr::null_mut(),
+        };
+        // Safety: the LUT lives in `self.proctex` until it is replaced and rebound
+        unsafe {
+            citro3d_sys::C3D_ProcTexColorLutBind(raw);
+        }
+    }
+
+    /// Start the gas accumulation pass. Gas drawn after this (with
+    /// [`FogMode::Gas`](fog::FogMode::Gas)) adds to the accumulated gas density.
+    ///
+    /// This only has an effect while a frame is being rendered.
+    #[doc(alias = "C3D_GasBeginAcc")]
+    pub fn begin_gas_accumulation(&mut self) {
+        unsafe {
+            citro3d_sys::C3D_GasBeginAcc();
+        }
+    }
+
+    /// Set the depth scale used when accumulating gas with
+    /// [`GasMode::DepthDensity`](fog::GasMode::DepthDensity).
+    #[doc(alias = "C3D_GasDeltaZ")]
+    pub fn set_gas_delta_z(&mut self, value: f32) {
+        unsafe {
+            citro3d_sys::C3D_GasDeltaZ(value);
+        }
+    }
+
+    /// Set the maximum accumulated gas density, which the density is
+    /// normalized against when shading.
+    #[doc(alias = "C3D_GasAccMax")]
+    pub fn set_gas_accumulation_max(&mut self, value: f32) {
+        unsafe {
+            citro3d_sys::C3D_GasAccMax(value);
+        }
+    }
+
+    /// Set the attenuation of the gas density when shading.
+    #[doc(alias = "C3D_GasAttn")]
+    pub fn set_gas_attenuation(&mut self, value: f32) {
+        unsafe {
+            citro3d_sys::C3D_GasAttn(value);
+        }
+    }
+
+    /// Set the range and attenuation of planar shading of the gas,
+    /// i.e. light coming from the direction set with [`Self::set_gas_light_direction`].
+    #[doc(alias = "C3D_GasLightPlanar")]
+    pub fn set_gas_light_planar(&mut self, min: f32, max: f32, attenuation: f32) {
+        unsafe {
+            citro3d_sys::C3D_GasLightPlanar(min, max, attenuation);
+        }
+    }
+
+    /// Set the range and attenuation of view shading of the gas, i.e.
+    /// light scattering towards the viewer.
+    #[doc(alias = "C3D_GasLightView")]
+    pub fn set_gas_light_view(&mut self, min: f32, max: f32, attenuation: f32) {
+        unsafe {
+            citro3d_sys::C3D_GasLightView(min, max, attenuation);
+        }
+    }
+
+    /// Set the light direction used for gas shading, given as the dot
+    /// product of the light direction and view vector.
+    #[doc(alias = "C3D_GasLightDirection")]
+    pub fn set_gas_light_direction(&mut self, dot_product: f32) {
+        unsafe {
+            citro3d_sys::C3D_GasLightDirection(dot_product);
+        }
+    }
+
+    /// Set which value is used to look up the gas LUT when shading.
+    #[doc(alias = "C3D_GasLutInput")]
+    pub fn set_gas_lut_input(&mut self, input: gas::LutInput) {
+        unsafe {
+            citro3d_sys::C3D_GasLutInput(input as ctru_sys::GPU_GASLUTINPUT);
+        }
+    }
+
+    /// Bind the LUT used to color gas when shading, or unbind it with `None`.
+    #[doc(alias = "C3D_GasLutBind")]
+    pub fn bind_gas_lut(&mut self, lut: Option<gas::GasLut>) {
+        self.gas_lut = lut.map(Box::new);
+
+        let raw = match &mut self.gas_lut {
+            Some(lut) => lut.as_raw_mut() as *mut _,
+            None => std::ptr::null_mut(),
+        };
+        // Safety: the LUT lives in `self.gas_lut` until it is replaced and rebound
+        unsafe {
+            citro3d_sys::C3D_GasLutBind(raw);
+        }
+    }
+
+    /// Render a frame. The passed in function/closure can mutate the instance,
+    /// such as to [select a render target](Self::select_render_target)
+    /// or [bind a new shader program](Self::bind_program).
+    ///
+    /// This is equivalent to calling [`Self::render_frame_with_flags`] with
+    /// [`FrameFlags::SYNC_DRAW`] when beginning the frame and no flags when
+    /// ending it.
+    ///
+    /// # Synchronization
+    ///
+    /// [`FrameFlags::SYNC_DRAW`] already paces frames to the display: before
+    /// beginning the frame, citro3d waits for the screens' next vertical blank.
+    /// Waiting for vblank again after rendering (e.g. with [`Self::wait_for_vblank`]
+    /// or [`ctru::services::gfx::Gfx::wait_for_vblank`]) would wait for a
+    /// second vblank, halving the frame rate to 30 FPS.
+    #[doc(alias = "C3D_FrameBegin")]
+    #[doc(alias = "C3D_FrameEnd")]
+    pub fn render_frame_with(&mut self, f: impl FnOnce(&mut Self)) {
+        self.render_frame_with_flags(FrameFlags::SYNC_DRAW, FrameFlags::empty(), f);
+    }
+
+    /// Render a frame, using `begin_flags` and `end_flags` to control how the
+    /// frame is started and submitted. The passed in function/closure can mutate
+    /// the instance, the same as with [`Self::render_frame_with`].
+    ///
+    /// `begin_flags` are passed to `C3D_FrameBegin` and `end_flags` to `C3D_FrameEnd`,
+    /// so they can be configured independently of one another.
+    ///
+    /// Returns whether the frame was rendered. With [`FrameFlags::NONBLOCK`],
+    /// the frame can't begin while the GPU is still processing the previous
+    /// one: `f` is not called and this returns `false`. See
+    /// [`Self::frame_in_progress`] to wait for the GPU without blocking.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::FrameFlags;
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// let rendered =
+    ///     instance.render_frame_with_flags(FrameFlags::NONBLOCK, FrameFlags::empty(), |_instance| {
+    ///         // draw calls go here
+    ///     });
+    ///
+    /// if !rendered {
+    ///     // the GPU was busy, try again later
+    /// }
+    /// ```
+    #[doc(alias = "C3D_FrameBegin")]
+    #[doc(alias = "C3D_FrameEnd")]
+    pub fn render_frame_with_flags(
+        &mut self,
+        begin_flags: FrameFlags,
+        end_flags: FrameFlags,
+        f: impl FnOnce(&mut Self),
+    ) -> bool {
+        if std::mem::take(&mut self.frame_begun) {
+            // `frame_in_progress` or `wait_frame` already began the frame,
+            // but without pacing it to the display
+            if begin_flags.contains(FrameFlags::SYNC_DRAW) {
+                unsafe {
+                    citro3d_sys::C3D_FrameSync();
+                }
+            }
+        } else if !unsafe { citro3d_sys::C3D_FrameBegin(begin_flags.bits()) } {
+            return false;
+        }
+        self.draw_calls = 0;
+        self.frame += 1;
+        if self.clip_plane.is_some() {
+            self.write_clip_plane();
+        }
+
+        f(self);
+
+        unsafe {
+            citro3d_sys::C3D_FrameEnd(end_flags.bits());
+        }
+        true
+    }
+
+    /// Check whether the GPU is still processing the previously submitted
+    /// frame, without blocking.
+    ///
+    /// While this returns `true`, beginning a new frame would have to wait
+    /// for the GPU (or fail, with [`FrameFlags::NONBLOCK`]), so the time can
+    /// be spent on CPU work instead. Once it returns `false`, the next frame
+    /// has already begun and the following call to [`Self::render_frame_with`]
+    /// or [`Self::render_frame_with_flags`] renders into it right away.
+    /// For this reason it takes `&mut self`, and the next frame should be
+    /// rendered soon after.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// # fn do_cpu_work() {}
+    /// instance.render_frame_with(|_instance| {
+    ///     // draw calls go here
+    /// });
+    ///
+    /// while instance.frame_in_progress() {
+    ///     do_cpu_work();
+    /// }
+    ///
+    /// instance.render_frame_with(|_instance| {
+    ///     // this frame begins without waiting for the GPU
+    /// });
+    /// ```
+    #[doc(alias = "C3D_FrameBegin")]
+    #[doc(alias = "C3D_FRAME_NONBLOCK")]
+    pub fn frame_in_progress(&mut self) -> bool {
+        if !self.frame_begun {
+            self.frame_begun = unsafe { citro3d_sys::C3D_FrameBegin(FrameFlags::NONBLOCK.bits()) };
+        }
+        !self.frame_begun
+    }
+
+    /// Block until the GPU has finished processing the previously submitted
+    /// frame. Like [`Self::frame_in_progress`], this begins the next frame,
+    /// which the following call to [`Self::render_frame_with`] renders into.
+    ///
+    /// This is only needed to synchronize with the GPU outside of rendering
+    /// (e.g. before reading back a render target), since beginning a frame
+    /// without [`FrameFlags::NONBLOCK`] already waits for it.
+    #[doc(alias = "C3D_FrameBegin")]
+    pub fn wait_frame(&mut self) {
+        if !self.frame_begun {
+            self.frame_begun = unsafe { citro3d_sys::C3D_FrameBegin(0) };
+        }
+    }
+
+    /// Register a closure to run at the end of every frame, replacing any
+    /// previously registered one.
+    ///
+    /// citro3d calls the hook synchronously on the CPU from within `C3D_FrameEnd`
+    /// (i.e. at the end of [`Self::render_frame_with`]), after the frame's draw
+    /// calls have been recorded but *before* its commands are submitted to the
+    /// GPU. The GPU has therefore not started, let alone finished, rendering the
+    /// frame when the hook runs, so it must not be used to reuse memory the
+    /// frame reads from. It is meant for per-frame CPU bookkeeping, such as
+    /// collecting statistics. To find out when the GPU is done with a frame,
+    /// use [`Self::frame_in_progress`] or [`Self::wait_frame`] instead.
+    ///
+    /// The closure must be `'static`, so it can't borrow the `Instance` or
+    /// anything used to render: it runs while the instance is mutably borrowed
+    /// by [`Self::render_frame_with`]. Share state with it through owned
+    /// handles (e.g. an [`Arc`] or a channel) instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use std::sync::Arc;
+    /// # use std::sync::atomic::{AtomicU32, Ordering};
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// let frames = Arc::new(AtomicU32::new(0));
+    ///
+    /// let counter = Arc::clone(&frames);
+    /// instance.set_frame_end_hook(move || {
+    ///     counter.fetch_add(1, Ordering::Relaxed);
+    /// });
+    ///
+    /// instance.render_frame_with(|_instance| {});
+    /// assert_eq!(frames.load(Ordering::Relaxed), 1);
+    /// ```
+    #[doc(alias = "C3D_FrameEndHook")]
+    pub fn set_frame_end_hook(&mut self, hook: impl FnMut() + Send + 'static) {
+        // Replacing the old hook is fine, since citro3d only calls it from
+        // `C3D_FrameEnd`, which can't run until this returns
+        let hook = self
+            .frame_end_hook
+            .insert(Box::new(FrameEndHook(Box::new(hook))));
+        let param: *mut FrameEndHook = &mut **hook;
+        unsafe {
+            citro3d_sys::C3D_FrameEndHook(Some(frame_end_trampoline), param.cast());
+        }
+    }
+
+    /// Unregister the hook set with [`Self::set_frame_end_hook`], if any.
+    #[doc(alias = "C3D_FrameEndHook")]
+    pub fn clear_frame_end_hook(&mut self) {
+        unsafe {
+            citro3d_sys::C3D_FrameEndHook(None, std::ptr::null_mut());
+        }
+        self.frame_end_hook = None;
+    }
 
-impl Instance {
-    /// Initialize the default `citro3d` instance.
+    /// Set the target frame rate used to pace frames begun with
+    /// [`FrameFlags::SYNC_DRAW`] (e.g. by [`Self::render_frame_with`]), and
+    /// return the previous frame rate.
     ///
-    /// # Errors
+    /// The default is 60 FPS, i.e. the display's refresh rate. A lower rate
+    /// like 30 FPS makes `SYNC_DRAW` wait for additional vertical blanks, so
+    /// frames are presented at a steady rate instead of alternating between
+    /// 30 and 60 FPS when some frames take longer than others.
     ///
-    /// Fails if `citro3d` cannot be initialized.
-    pub fn new() -> Result<Self> {
-        Self::with_cmdbuf_size(citro3d_sys::C3D_DEFAULT_CMDBUF_SIZE.try_into().unwrap())
+    /// Only rates between `0.0` (exclusive) and `60.0` (inclusive) are
+    /// accepted; other values, including non-positive ones, leave the frame
+    /// rate unchanged. To stop pacing frames altogether, render without
+    /// [`FrameFlags::SYNC_DRAW`] using [`Self::render_frame_with_flags`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// instance.set_frame_rate(30.0);
+    /// assert_eq!(instance.frame_rate(), 30.0);
+    ///
+    /// // Out of range values are ignored
+    /// assert_eq!(instance.set_frame_rate(0.0), 30.0);
+    /// assert_eq!(instance.frame_rate(), 30.0);
+    /// ```
+    #[doc(alias = "C3D_FrameRate")]
+    pub fn set_frame_rate(&mut self, fps: f32) -> f32 {
+        unsafe { citro3d_sys::C3D_FrameRate(fps) }
     }
 
-    /// Initialize the instance with a specified command buffer size.
-    ///
-    /// # Errors
+    /// Get the target frame rate set with [`Self::set_frame_rate`].
+    #[doc(alias = "C3D_FrameRate")]
+    pub fn frame_rate(&self) -> f32 {
+        // Non-positive values only query the current rate
+        unsafe { citro3d_sys::C3D_FrameRate(0.0) }
+    }
+
+    /// Block until the next vertical blank of the top screen, i.e. the point
+    /// at which the display starts scanning out a new frame.
     ///
-    /// Fails if `citro3d` cannot be initialized.
-    #[doc(alias = "C3D_Init")]
-    pub fn with_cmdbuf_size(size: usize) -> Result<Self> {
-        if unsafe { citro3d_sys::C3D_Init(size) } {
-            let mut light_env = Box::pin(light::LightEnv::new());
-            unsafe {
-                // setup the light env slot, since this is a pointer copy it will stick around even with we swap
-                // out light_env later
-                citro3d_sys::C3D_LightEnvBind(light_env.as_mut().as_raw_mut());
-            }
-            Ok(Self {
-                texenvs: std::array::from_fn(|_| OnceLock::new()),
-                shader: None,
-                light_env,
-            })
-        } else {
-            Err(Error::FailedToInitialize)
+    /// This is only needed when frames are not already synchronized to the
+    /// display by [`FrameFlags::SYNC_DRAW`], e.g. when rendering with
+    /// [`Self::render_frame_with_flags`] without it. Draw synchronization
+    /// (waiting for the GPU to finish its work) and display synchronization
+    /// (waiting for vblank) are independent; see
+    /// [`Self::render_frame_with`] for details.
+    #[doc(alias = "gspWaitForVBlank")]
+    pub fn wait_for_vblank(&self) {
+        unsafe {
+            ctru_sys::gspWaitForEvent(ctru_sys::GSPGPU_EVENT_VBlank0, true);
         }
     }
 
-    /// Select the given render target for drawing the frame.
+    /// Get how much of the command buffer is currently in use, as a fraction
+    /// between `0.0` and `1.0`.
     ///
-    /// # Errors
+    /// Running out of command buffer space crashes, so this can be used to check
+    /// how close a frame gets to the limit, e.g. to pick a larger size for
+    /// [`Self::with_cmdbuf_size`].
+    #[doc(alias = "C3D_GetCmdBufUsage")]
+    pub fn cmdbuf_usage(&self) -> f32 {
+        unsafe { citro3d_sys::C3D_GetCmdBufUsage() }
+    }
+
+    /// Get the size of the command buffer, in bytes, as given to
+    /// [`Self::with_cmdbuf_size`].
     ///
-    /// Fails if the given target cannot be used for drawing.
-    #[doc(alias = "C3D_FrameDrawOn")]
-    pub fn select_render_target(&mut self, target: &render::Target<'_>) -> Result<()> {
-        let _ = self;
-        if unsafe { citro3d_sys::C3D_FrameDrawOn(target.as_raw()) } {
-            Ok(())
-        } else {
-            Err(Error::InvalidRenderTarget)
-        }
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let instance = citro3d::Instance::new(&gfx).unwrap();
+    /// let used = instance.cmdbuf_usage() * instance.cmdbuf_size() as f32;
+    /// let headroom = instance.cmdbuf_size() as f32 - used;
+    /// assert!((0.0..=instance.cmdbuf_size() as f32).contains(&headroom));
+    /// ```
+    pub fn cmdbuf_size(&self) -> usize {
+        self.cmdbuf_size
     }
 
-    /// Render a frame. The passed in function/closure can mutate the instance,
-    /// such as to [select a render target](Self::select_render_target)
-    /// or [bind a new shader program](Self::bind_program).
-    #[doc(alias = "C3D_FrameBegin")]
-    #[doc(alias = "C3D_FrameEnd")]
-    pub fn render_frame_with(&mut self, f: impl FnOnce(&mut Self)) {
-        unsafe {
-            citro3d_sys::C3D_FrameBegin(
-                // TODO: begin + end flags should be configurable
-                citro3d_sys::C3D_FRAME_SYNCDRAW.try_into().unwrap(),
-            );
-        }
+    /// Get the number of draw calls made since the current (or last) frame began.
+    ///
+    /// citro3d doesn't keep track of this, so only draw calls made through this
+    /// [`Instance`] are counted.
+    pub fn draw_call_count(&self) -> usize {
+        self.draw_calls
+    }
 
-        f(self);
+    /// Get the time the GPU spent drawing the last rendered frame.
+    ///
+    /// The PICA200 has no occlusion queries or fragment counters, so this is the
+    /// closest measure of how expensive the submitted draw calls were.
+    #[doc(alias = "C3D_GetDrawingTime")]
+    pub fn drawing_time(&self) -> Duration {
+        Duration::from_secs_f32(unsafe { citro3d_sys::C3D_GetDrawingTime() } / 1000.0)
+    }
 
-        unsafe {
-            citro3d_sys::C3D_FrameEnd(0);
-        }
+    /// Get the time the GPU spent processing the command list of the last
+    /// rendered frame.
+    #[doc(alias = "C3D_GetProcessingTime")]
+    pub fn processing_time(&self) -> Duration {
+        Duration::from_secs_f32(unsafe { citro3d_sys::C3D_GetProcessingTime() } / 1000.0)
     }
 
     /// Get the buffer info being used, if it exists. Note that the resulting
@@ -165,12 +1416,47 @@ impl Instance {
         unsafe { citro3d_sys::C3D_SetAttrInfo(raw.cast_mut()) };
     }
 
+    /// Set the value of a fixed attribute, which is used for every vertex in
+    /// following draw calls. The attribute should have been added with
+    /// [`attrib::Info::add_fixed`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::{attrib, math::FVec4};
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// let mut attr_info = attrib::Info::new();
+    /// attr_info.add_loader(attrib::Register::new(0).unwrap(), attrib::Format::Float, 3).unwrap();
+    /// let color = attr_info.add_fixed(attrib::Register::new(1).unwrap()).unwrap();
+    /// instance.set_attr_info(&attr_info);
+    ///
+    /// instance.set_fixed_attribute(color, FVec4::new(1.0, 0.0, 0.0, 1.0));
+    /// ```
+    #[doc(alias = "C3D_FixedAttribSet")]
+    #[doc(alias = "C3D_FixedAttribGetWritePtr")]
+    pub fn set_fixed_attribute(&mut self, index: attrib::Index, value: math::FVec4) {
+        unsafe {
+            citro3d_sys::C3D_FixedAttribSet(
+                index.into(),
+                value.x(),
+                value.y(),
+                value.z(),
+                value.w(),
+            );
+        }
+    }
+
     /// Render primitives from the current vertex array buffer.
+    ///
+    /// This uses whichever [`attrib::Info`] was last bound with
+    /// [`Self::set_attr_info`]; use [`Self::draw_arrays_with`] to bind it as part
+    /// of the draw call instead.
     #[doc(alias = "C3D_DrawArrays")]
     pub fn draw_arrays(&mut self, primitive: buffer::Primitive, vbo_data: buffer::Slice) {
         self.set_buffer_info(vbo_data.info());
 
-        // TODO: should we also require the attrib info directly here?
         unsafe {
             citro3d_sys::C3D_DrawArrays(
                 primitive as ctru_sys::GPU_Primitive_t,
@@ -178,44 +1464,181 @@ impl Instance {
                 vbo_data.len(),
             );
         }
+        self.draw_calls += 1;
     }
-    /// Indexed drawing
+
+    /// Render primitives from a vertex array buffer, using the given attribute
+    /// layout. This is the same as calling [`Self::set_attr_info`] followed by
+    /// [`Self::draw_arrays`], so the attribute info can't accidentally be left
+    /// over from a previous draw.
+    ///
+    /// # Example
     ///
-    /// Draws the vertices in `buf` indexed by `indices`. `indices` must be linearly allocated
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::attrib::{self, Format, Register};
+    /// # use citro3d::buffer::{self, LinearVec, Primitive};
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// let mut builder = attrib::Info::builder();
+    /// builder.add(Register::new(0).unwrap(), Format::Float, 3).unwrap();
+    /// let attr_info = builder.build();
+    /// let vertices = LinearVec::from_slice(&[[0.0_f32; 3]; 3]);
     ///
-    /// # Safety
-    /// If `indices` goes out of scope before the current frame ends it will cause a use-after-free (possibly by the GPU)
-    /// If `buf` does not contain all the vertices references by `indices` it will cause an invalid access by the GPU (this crashes citra)
+    /// let mut buf_info = buffer::Info::new();
+    /// let vbo = buf_info.add(&vertices, &attr_info).unwrap();
+    ///
+    /// instance.draw_arrays_with(Primitive::Triangles, vbo, &attr_info);
+    /// ```
+    #[doc(alias = "C3D_DrawArrays")]
+    pub fn draw_arrays_with(
+        &mut self,
+        primitive: buffer::Primitive,
+        vbo_data: buffer::Slice,
+        attr_info: &attrib::Info,
+    ) {
+        self.set_attr_info(attr_info);
+        self.draw_arrays(primitive, vbo_data);
+    }
+
+    /// Draw the same vertices once per item of `transforms`, binding each one
+    /// to the vertex shader uniform at `index` before its draw call.
+    ///
+    /// The PICA has no hardware instancing, so this still issues one
+    /// `C3D_DrawArrays` per transform, but the buffer info is only set once and
+    /// only the uniform is updated between draws. This is useful for drawing
+    /// many copies of the same model, with `index` pointing at its model matrix.
     ///
     /// # Panics
-    /// If `indices` is not allocated in linear memory
+    ///
+    /// If a transform cannot be bound at `index`; see [`Self::bind_vertex_uniform`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::buffer::{self, Primitive};
+    /// # use citro3d::math::{FVec3, Matrix4};
+    /// # use citro3d::uniform;
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// # let vertices = citro3d::buffer::LinearVec::from_slice(&[[0.0_f32; 3]; 36]);
+    /// # let mut buf_info = buffer::Info::new();
+    /// # let mut attr_info = citro3d::attrib::Info::new();
+    /// # let reg = citro3d::attrib::Register::new(0).unwrap();
+    /// # attr_info.add_loader(reg, citro3d::attrib::Format::Float, 3).unwrap();
+    /// # let crate_vbo = buf_info.add(&vertices, &attr_info).unwrap();
+    /// let model_matrix = uniform::Index::from(4);
+    ///
+    /// instance.draw_arrays_batched(
+    ///     Primitive::Triangles,
+    ///     crate_vbo,
+    ///     model_matrix,
+    ///     (0..200).map(|i| {
+    ///         let mut model = Matrix4::identity();
+    ///         model.translate(i as f32 * 2.0, 0.0, 0.0);
+    ///         model
+    ///     }),
+    /// );
+    /// assert_eq!(instance.draw_call_count(), 200);
+    /// ```
+    #[doc(alias = "C3D_DrawArrays")]
+    pub fn draw_arrays_batched<I>(
+        &mut self,
+        primitive: buffer::Primitive,
+        vbo_data: buffer::Slice,
+        index: uniform::Index,
+        transforms: I,
+    ) where
+        I: IntoIterator,
+        I::Item: Into<Uniform>,
+    {
+        self.set_buffer_info(vbo_data.info());
+
+        for transform in transforms {
+            self.bind_vertex_uniform(index, transform);
+            unsafe {
+                citro3d_sys::C3D_DrawArrays(
+                    primitive as ctru_sys::GPU_Primitive_t,
+                    vbo_data.index(),
+                    vbo_data.len(),
+                );
+            }
+            self.draw_calls += 1;
+        }
+    }
+
+    /// Draw primitives from vertices sent directly in the command buffer,
+    /// using the attribute layout of the currently bound [`attrib::Info`].
+    ///
+    /// The vertices are submitted through the [`immediate::Immediate`] passed to `f`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::{attrib, buffer::Primitive, math::FVec4};
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// let mut attr_info = attrib::Info::new();
+    /// attr_info.add_loader(attrib::Register::new(0).unwrap(), attrib::Format::Float, 3).unwrap();
+    /// attr_info.add_loader(attrib::Register::new(1).unwrap(), attrib::Format::Float, 3).unwrap();
+    /// instance.set_attr_info(&attr_info);
+    ///
+    /// let red = FVec4::new(1.0, 0.0, 0.0, 1.0);
+    /// instance.draw_immediate(Primitive::Triangles, |imm| {
+    ///     imm.vertex(&[FVec4::new(0.0, 0.5, 0.5, 1.0), red]);
+    ///     imm.vertex(&[FVec4::new(-0.5, -0.5, 0.5, 1.0), red]);
+    ///     imm.vertex(&[FVec4::new(0.5, -0.5, 0.5, 1.0), red]);
+    /// });
+    /// ```
+    #[doc(alias = "C3D_ImmDrawBegin")]
+    #[doc(alias = "C3D_ImmDrawEnd")]
+    pub fn draw_immediate<F>(&mut self, primitive: buffer::Primitive, f: F)
+    where
+        F: FnOnce(&mut immediate::Immediate),
+    {
+        // The context always has attribute info while the instance is alive,
+        // though it may have no attributes if none were set.
+        let attr_count = self
+            .attr_info()
+            .expect("citro3d context should have attribute info")
+            .attr_count();
+        let mut imm = immediate::Immediate::new(attr_count as usize);
+
+        unsafe {
+            citro3d_sys::C3D_ImmDrawBegin(primitive as ctru_sys::GPU_Primitive_t);
+        }
+        f(&mut imm);
+        unsafe {
+            citro3d_sys::C3D_ImmDrawEnd();
+        }
+        self.draw_calls += 1;
+    }
+
+    /// Indexed drawing. Draws the vertices in `buf` indexed by `indices`.
+    ///
+    /// # Safety
+    ///
+    /// * If `indices` goes out of scope before the current frame ends it will cause
+    ///   a use-after-free (possibly by the GPU).
+    /// * If `buf` does not contain all the vertices referenced by `indices` it will
+    ///   cause an invalid access by the GPU (this crashes citra).
     #[doc(alias = "C3D_DrawElements")]
-    pub unsafe fn draw_elements<'a>(
+    pub unsafe fn draw_elements(
         &mut self,
         primitive: buffer::Primitive,
         buf: &buffer::Info,
-        indices: impl Into<IndexType<'a>>,
+        indices: &buffer::IndexBuffer<'_>,
     ) {
         self.set_buffer_info(buf);
-        let indices: IndexType<'a> = indices.into();
-        let elements = match indices {
-            IndexType::U16(v) => v.as_ptr() as *const _,
-            IndexType::U8(v) => v.as_ptr() as *const _,
-        };
-        assert!(
-            is_linear_ptr(elements),
-            "draw_elements requires linear allocated indices buffer"
-        );
         citro3d_sys::C3D_DrawElements(
             primitive as ctru_sys::GPU_Primitive_t,
             indices.len() as i32,
-            // flag bit for short or byte
-            match indices {
-                IndexType::U16(_) => citro3d_sys::C3D_UNSIGNED_SHORT,
-                IndexType::U8(_) => citro3d_sys::C3D_UNSIGNED_BYTE,
-            } as i32,
-            elements,
+            indices.format() as i32,
+            indices.as_ptr(),
         );
+        self.draw_calls += 1;
     }
 
     /// Use the given [`shader::Program`] for subsequent draw calls.
@@ -233,38 +1656,305 @@ impl Instance {
 
     /// Bind a uniform to the given `index` in the vertex shader for the next draw call.
     ///
+    /// # Panics
+    ///
+    /// If `index` is out of the valid range for the given uniform type, or if the
+    /// uniform would not fit in the remaining registers starting at `index`.
+    /// See [`Uniform::index_range`].
+    ///
     /// # Example
     ///
     /// ```
     /// # let _runner = test_runner::GdbRunner::default();
     /// # use citro3d::uniform;
-    /// # use citro3d::math::Matrix;
+    /// # use citro3d::math::Matrix4;
     /// #
-    /// # let mut instance = citro3d::Instance::new().unwrap();
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
     /// let idx = uniform::Index::from(0);
-    /// let mtx = Matrix::identity();
+    /// let mtx = Matrix4::identity();
     /// instance.bind_vertex_uniform(idx, &mtx);
+    ///
+    /// // plain arrays and tuples are converted to `FVec4` (or `IVec` for `u8`s)
+    /// let color = uniform::Index::from(4);
+    /// instance.bind_vertex_uniform(color, [1.0, 0.0, 0.0, 1.0]);
     /// ```
     pub fn bind_vertex_uniform(&mut self, index: uniform::Index, uniform: impl Into<Uniform>) {
-        uniform.into().bind(self, shader::Type::Vertex, index);
+        self.bind_uniform(shader::Type::Vertex, index, uniform);
     }
 
     /// Bind a uniform to the given `index` in the geometry shader for the next draw call.
     ///
+    /// # Panics
+    ///
+    /// If `index` is out of the valid range for the given uniform type, or if the
+    /// uniform would not fit in the remaining registers starting at `index`.
+    /// See [`Uniform::index_range`].
+    ///
     /// # Example
     ///
     /// ```
     /// # let _runner = test_runner::GdbRunner::default();
     /// # use citro3d::uniform;
-    /// # use citro3d::math::Matrix;
+    /// # use citro3d::math::Matrix4;
     /// #
-    /// # let mut instance = citro3d::Instance::new().unwrap();
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
     /// let idx = uniform::Index::from(0);
-    /// let mtx = Matrix::identity();
+    /// let mtx = Matrix4::identity();
     /// instance.bind_geometry_uniform(idx, &mtx);
     /// ```
     pub fn bind_geometry_uniform(&mut self, index: uniform::Index, uniform: impl Into<Uniform>) {
-        uniform.into().bind(self, shader::Type::Geometry, index);
+        self.bind_uniform(shader::Type::Geometry, index, uniform);
+    }
+
+    /// Bind a uniform to the given `index` in the shader of the given [`shader::Type`]
+    /// for the next draw call.
+    ///
+    /// [`Self::bind_vertex_uniform`] and [`Self::bind_geometry_uniform`] are shorthands
+    /// for this method.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of the valid range for the given uniform type, or if the
+    /// uniform would not fit in the remaining registers starting at `index`.
+    /// See [`Uniform::index_range`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::{shader, uniform};
+    /// # use citro3d::math::Matrix4;
+    /// #
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// let idx = uniform::Index::from(0);
+    /// let mtx = Matrix4::identity();
+    /// instance.bind_uniform(shader::Type::Geometry, idx, &mtx);
+    /// ```
+    #[doc(alias = "C3D_FVUnifMtx4x4")]
+    #[doc(alias = "C3D_FVUnifSet")]
+    pub fn bind_uniform(
+        &mut self,
+        shader_type: shader::Type,
+        index: uniform::Index,
+        uniform: impl Into<Uniform>,
+    ) {
+        if let Err(err) = self.try_bind_uniform(shader_type, index, uniform) {
+            panic!("failed to bind uniform: {err}");
+        }
+    }
+
+    /// Bind a uniform like [`Self::bind_uniform`], returning an error instead of
+    /// panicking if it cannot be bound at the given `index`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Uniform::check_index`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::{shader, uniform};
+    /// # use citro3d::math::Matrix4;
+    /// #
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// let mtx = Matrix4::identity();
+    /// let result = instance.try_bind_uniform(shader::Type::Vertex, uniform::Index::from(0x5E), &mtx);
+    /// assert!(matches!(result, Err(citro3d::Error::UniformOverflow { .. })));
+    /// ```
+    pub fn try_bind_uniform(
+        &mut self,
+        shader_type: shader::Type,
+        index: uniform::Index,
+        uniform: impl Into<Uniform>,
+    ) -> Result<()> {
+        uniform.into().bind(self, shader_type, index)
+    }
+
+    /// Bind an array of float vectors to consecutive registers of the vertex
+    /// shader, starting at `index`. This is useful for uniform arrays of any
+    /// length, e.g. `.fvec bones[16]` for skinning.
+    ///
+    /// # Panics
+    ///
+    /// If the array doesn't fit in the float uniform registers (`0x00..0x60`)
+    /// starting at `index`. See [`Self::try_bind_uniform_array`] for a
+    /// non-panicking version.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::FVec4;
+    /// # use citro3d::uniform;
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// let bones = [FVec4::new(0.0, 0.0, 0.0, 1.0); 16];
+    /// instance.bind_vertex_uniform_array(uniform::Index::from(0x20), &bones);
+    /// ```
+    #[doc(alias = "C3D_FVUnifSet")]
+    pub fn bind_vertex_uniform_array(&mut self, index: uniform::Index, values: &[math::FVec4]) {
+        if let Err(err) = self.try_bind_uniform_array(shader::Type::Vertex, index, values) {
+            panic!("failed to bind uniform array: {err}");
+        }
+    }
+
+    /// Bind an array of float vectors to consecutive registers of the shader of
+    /// the given [`shader::Type`], starting at `index`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidUniformIndex`] if `index` is not a float register
+    /// * [`Error::UniformOverflow`] if the array would not fit in the remaining
+    ///   float registers starting at `index`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::FVec4;
+    /// # use citro3d::{shader, uniform};
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// let values = [FVec4::splat(1.0); 8];
+    /// let result =
+    ///     instance.try_bind_uniform_array(shader::Type::Vertex, uniform::Index::from(0x5C), &values);
+    /// assert!(matches!(result, Err(citro3d::Error::UniformOverflow { .. })));
+    /// ```
+    #[doc(alias = "C3D_FVUnifSet")]
+    pub fn try_bind_uniform_array(
+        &mut self,
+        shader_type: shader::Type,
+        index: uniform::Index,
+        values: &[math::FVec4],
+    ) -> Result<()> {
+        uniform::bind_float_array(self, shader_type, index, values)
+    }
+
+    /// Get a writable slice of `count` consecutive float uniform registers of
+    /// the vertex shader, starting at `start`.
+    ///
+    /// This is more efficient than binding uniforms one at a time when filling
+    /// a large block of registers, e.g. a matrix palette for skinning. The
+    /// registers are uploaded with the next draw call. The slice borrows the
+    /// instance mutably, so nothing else can touch the uniforms while it is
+    /// alive.
+    ///
+    /// # Panics
+    ///
+    /// If the registers don't fit in the float uniform registers (`0x00..0x60`)
+    /// starting at `start`. See [`Self::try_uniform_write`] for a non-panicking
+    /// version.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::Matrix4;
+    /// # use citro3d::uniform;
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// let palette = [Matrix4::identity(); 4];
+    ///
+    /// let registers = instance.vertex_uniform_write(uniform::Index::from(0x10), 16);
+    /// for (rows, matrix) in registers.chunks_exact_mut(4).zip(&palette) {
+    ///     rows.copy_from_slice(&matrix.rows_wzyx());
+    /// }
+    /// ```
+    #[doc(alias = "C3D_FVUnifWritePtr")]
+    pub fn vertex_uniform_write(
+        &mut self,
+        start: uniform::Index,
+        count: usize,
+    ) -> &mut [math::FVec4] {
+        match self.try_uniform_write(shader::Type::Vertex, start, count) {
+            Ok(registers) => registers,
+            Err(err) => panic!("failed to write uniforms: {err}"),
+        }
+    }
+
+    /// Get a writable slice of `count` consecutive float uniform registers of
+    /// the shader of the given [`shader::Type`], starting at `start`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidUniformIndex`] if `start` is not a float register
+    /// * [`Error::UniformOverflow`] if `count` registers would not fit in the
+    ///   remaining float registers starting at `start`
+    #[doc(alias = "C3D_FVUnifWritePtr")]
+    pub fn try_uniform_write(
+        &mut self,
+        shader_type: shader::Type,
+        start: uniform::Index,
+        count: usize,
+    ) -> Result<&mut [math::FVec4]> {
+        uniform::float_array_mut(self, shader_type, start, count)
+    }
+
+    /// Choose which [`TexEnv`] stages write their output to the combiner
+    /// buffer, replacing any previous configuration for the given `mode`.
+    ///
+    /// Later stages can read the buffer with
+    /// [`Source::PreviousBuffer`](texenv::Source::PreviousBuffer), which allows
+    /// combining the output of non-adjacent stages (e.g. blending a detail
+    /// texture applied in stage 0 with the result of stage 2). The buffer lags
+    /// one stage behind, so the output of stage `n` can be read from stage
+    /// `n + 2` onwards; before any stage updates it, the buffer holds the color
+    /// set by [`Self::set_texenv_buffer_color`].
+    ///
+    /// # Panics
+    ///
+    /// If any of the `stages` can't update the buffer, i.e. is not one of
+    /// stages 0 to 3 (see [`Stage::can_update_buffer`](texenv::Stage::can_update_buffer)).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::texenv::{CombineFunc, Mode, Source, Stage};
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+    /// let stage0 = Stage::new(0).unwrap();
+    /// instance.set_texenv_buffer_update(Mode::BOTH, [stage0]);
+    ///
+    /// // ... stage 1 uses stage 0's output as `Source::Previous` ...
+    ///
+    /// instance
+    ///     .texenv(Stage::new(2).unwrap())
+    ///     .src(Mode::BOTH, Source::Previous, Some(Source::PreviousBuffer), None)
+    ///     .func(Mode::BOTH, CombineFunc::Modulate);
+    /// ```
+    #[doc(alias = "C3D_TexEnvBufUpdate")]
+    pub fn set_texenv_buffer_update(
+        &mut self,
+        mode: texenv::Mode,
+        stages: impl IntoIterator<Item = texenv::Stage>,
+    ) {
+        let mask = stages.into_iter().fold(0, |mask, stage| {
+            assert!(
+                stage.can_update_buffer(),
+                "texenv stage {} cannot update the combiner buffer",
+                stage.0
+            );
+            mask | 1 << stage.0
+        });
+
+        unsafe {
+            citro3d_sys::C3D_TexEnvBufUpdate(mode.bits() as _, mask);
+        }
+    }
+
+    /// Set the initial color of the combiner buffer, packed as `0xAABBGGRR`.
+    /// See [`Self::set_texenv_buffer_update`].
+    #[doc(alias = "C3D_TexEnvBufColor")]
+    pub fn set_texenv_buffer_color(&mut self, color: u32) {
+        unsafe {
+            citro3d_sys::C3D_TexEnvBufColor(color);
+        }
     }
 
     /// Retrieve the [`TexEnv`] for the given stage, initializing it first if necessary.
@@ -274,7 +1964,8 @@ impl Instance {
     /// ```
     /// # use citro3d::texenv;
     /// # let _runner = test_runner::GdbRunner::default();
-    /// # let mut instance = citro3d::Instance::new().unwrap();
+    /// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+    /// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
     /// let stage0 = texenv::Stage::new(0).unwrap();
     /// let texenv0 = instance.texenv(stage0);
     /// ```
@@ -289,21 +1980,41 @@ impl Instance {
     }
 }
 
-impl Drop for Instance {
+impl Drop for Instance<'_> {
     #[doc(alias = "C3D_Fini")]
     fn drop(&mut self) {
         unsafe {
+            citro3d_sys::C3D_FrameEndHook(None, std::ptr::null_mut());
             citro3d_sys::C3D_Fini();
         }
     }
 }
 
-assert_impl_all!(Instance: Send, Sync);
+assert_impl_all!(Instance<'static>: Send, Sync);
 
+bitflags::bitflags! {
+    /// Flags controlling how a frame is started or ended.
+    /// See [`Instance::render_frame_with_flags`].
+    #[doc(alias = "C3D_FRAME_SYNCDRAW")]
+    #[doc(alias = "C3D_FRAME_NONBLOCK")]
+    pub struct FrameFlags: u8 {
+        /// Synchronize the frame to the display: before beginning a new frame,
+        /// wait for the next vertical blank (see [`Instance::render_frame_with`]).
+        const SYNC_DRAW = citro3d_sys::C3D_FRAME_SYNCDRAW as u8;
+        /// Do not block waiting for the GPU if it is still busy processing
+        /// previously submitted commands.
+        const NONBLOCK = citro3d_sys::C3D_FRAME_NONBLOCK as u8;
+    }
+}
+
+/// A slice of vertex indices of either supported [`buffer::IndexFormat`].
+/// See [`buffer::IndexBuffer`].
+#[derive(Debug, Clone, Copy)]
 pub enum IndexType<'a> {
     U16(&'a [u16]),
     U8(&'a [u8]),
 }
+
 impl IndexType<'_> {
     fn len(&self) -> usize {
         match self {
@@ -311,6 +2022,27 @@ impl IndexType<'_> {
             IndexType::U8(a) => a.len(),
         }
     }
+
+    fn format(&self) -> buffer::IndexFormat {
+        match self {
+            IndexType::U16(_) => buffer::IndexFormat::UnsignedShort,
+            IndexType::U8(_) => buffer::IndexFormat::UnsignedByte,
+        }
+    }
+
+    fn as_ptr(&self) -> *const libc::c_void {
+        match self {
+            IndexType::U16(a) => a.as_ptr().cast(),
+            IndexType::U8(a) => a.as_ptr().cast(),
+        }
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            IndexType::U16(a) => bytemuck::cast_slice(a),
+            IndexType::U8(a) => a,
+        }
+    }
 }
 
 impl<'a> From<&'a [u8]> for IndexType<'a> {
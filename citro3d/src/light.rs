@@ -19,13 +19,15 @@
 //! Lets say we have this code
 //!
 //! ```
-//! # use citro3d::{Instance, light::{LightLutId, LightInput, LightLut}};
-//! let mut inst = Instance::new();
+//! # let _runner = test_runner::GdbRunner::default();
+//! # use citro3d::{Instance, light::{LightLutId, LutInput, LightLut}};
+//! # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+//! let mut inst = Instance::new(&gfx).unwrap();
 //! let mut env = inst.light_env_mut();
 //! env.as_mut().connect_lut(
-//!     LutInputId::D0,
+//!     LightLutId::D0,
 //!     LutInput::NormalView,
-//!     LightLut::from_fn(|x| x.powf(10.0)),
+//!     LightLut::from_fn(|x| x.powf(10.0), false),
 //! );
 //! ```
 //!
@@ -34,7 +36,29 @@
 //! the return of the closure goes out on the corresponding wire
 //! (which in the case of `D0` is used for specular lighting after being combined with with specular0)
 //!
+//! # Diffuse lighting
+//! Simple Lambert diffuse lighting doesn't need any LUTs, just a [`Material`] with a diffuse colour
+//! and a light, which can be set up with a [`LightBuilder`]:
 //!
+//! ```
+//! # let _runner = test_runner::GdbRunner::default();
+//! # use citro3d::{Instance, light::LightBuilder, material::{Color, Material}, math::FVec3};
+//! # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+//! let mut inst = Instance::new(&gfx).unwrap();
+//! let mut env = inst.light_env_mut();
+//! env.as_mut().set_material(Material {
+//!     ambient: Some(Color::new(0.1, 0.1, 0.1)),
+//!     diffuse: Some(Color::new(1.0, 0.5, 0.2)),
+//!     ..Default::default()
+//! });
+//! env.as_mut()
+//!     .add_light(LightBuilder::directional(FVec3::new(0.0, 0.0, -1.0)).color(1.0, 1.0, 1.0))
+//!     .unwrap();
+//! ```
+//!
+//! The shader then needs to output a normal quaternion and view vector, and the fragment
+//! lighting results can be used in a [`TexEnv`](crate::texenv::TexEnv) through
+//! [`Source::FragmentPrimaryColor`](crate::texenv::Source::FragmentPrimaryColor).
 //!
 //! [hardware]: https://raw.githubusercontent.com/wwylele/misc-3ds-diagram/master/pica-pipeline.svg
 
@@ -54,7 +78,7 @@ use crate::{
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct LightIndex(u8);
 
-const NB_LIGHTS: usize = 8;
+const NB_LIGHTS: usize = crate::limits::MAX_LIGHTS;
 
 impl LightIndex {
     /// Manually create a `LightIndex` with a specific index
@@ -181,6 +205,14 @@ impl LightEnv {
         );
         Some(LightIndex::new(idx))
     }
+    /// Create a light with the parameters from `builder`, returning its index.
+    ///
+    /// Returns `None` if all of the hardware lights are already in use.
+    pub fn add_light(mut self: Pin<&mut Self>, builder: LightBuilder) -> Option<LightIndex> {
+        let idx = self.as_mut().create_light()?;
+        builder.apply(self.light_mut(idx).unwrap());
+        Some(idx)
+    }
     fn lut_id_to_index(id: LightLutId) -> Option<usize> {
         match id {
             LightLutId::D0 => Some(0),
@@ -278,10 +310,21 @@ impl Light {
     fn as_raw_mut(self: Pin<&mut Self>) -> &mut citro3d_sys::C3D_Light {
         unsafe { &mut self.get_unchecked_mut().raw }
     }
+    /// Make this a point light at position `p`.
+    #[doc(alias = "C3D_LightPosition")]
     pub fn set_position(self: Pin<&mut Self>, p: FVec3) {
         let mut p = FVec4::new(p.x(), p.y(), p.z(), 1.0);
         unsafe { citro3d_sys::C3D_LightPosition(self.as_raw_mut(), &mut p.0) }
     }
+    /// Make this a directional light, shining in direction `d`.
+    #[doc(alias = "C3D_LightPosition")]
+    pub fn set_direction(self: Pin<&mut Self>, d: FVec3) {
+        // citro3d treats a position with w = 0 as a direction (pointing towards the light)
+        let mut p = FVec4::new(-d.x(), -d.y(), -d.z(), 0.0);
+        unsafe { citro3d_sys::C3D_LightPosition(self.as_raw_mut(), &mut p.0) }
+    }
+    /// Set the diffuse and specular colour of the light.
+    #[doc(alias = "C3D_LightColor")]
     pub fn set_color(self: Pin<&mut Self>, r: f32, g: f32, b: f32) {
         unsafe { citro3d_sys::C3D_LightColor(self.as_raw_mut(), r, g, b) }
     }
@@ -319,6 +362,83 @@ impl Light {
     }
 }
 
+#[derive(Clone, Copy, Debug)]
+enum LightPlacement {
+    Point(FVec3),
+    Directional(FVec3),
+}
+
+/// Builder for the parameters of a [`Light`], to be passed to [`LightEnv::add_light`].
+///
+/// By default the light is white, enabled and doesn't cast shadows.
+pub struct LightBuilder {
+    placement: LightPlacement,
+    color: [f32; 3],
+    enabled: bool,
+    shadow: bool,
+    distance_attenuation: Option<LightLutDistAtten>,
+}
+
+impl LightBuilder {
+    /// A point light at `position`.
+    pub fn point(position: FVec3) -> Self {
+        Self::new(LightPlacement::Point(position))
+    }
+
+    /// A directional light shining in `direction`, like the sun.
+    pub fn directional(direction: FVec3) -> Self {
+        Self::new(LightPlacement::Directional(direction))
+    }
+
+    fn new(placement: LightPlacement) -> Self {
+        Self {
+            placement,
+            color: [1.0; 3],
+            enabled: true,
+            shadow: false,
+            distance_attenuation: None,
+        }
+    }
+
+    /// Set the diffuse and specular colour of the light.
+    pub fn color(mut self, r: f32, g: f32, b: f32) -> Self {
+        self.color = [r, g, b];
+        self
+    }
+
+    /// Set whether the light starts out enabled.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Set whether the light is affected by shadows.
+    pub fn shadow(mut self, shadow: bool) -> Self {
+        self.shadow = shadow;
+        self
+    }
+
+    /// Set the distance attenuation LUT for the light.
+    pub fn distance_attenuation(mut self, lut: LightLutDistAtten) -> Self {
+        self.distance_attenuation = Some(lut);
+        self
+    }
+
+    fn apply(self, mut light: Pin<&mut Light>) {
+        match self.placement {
+            LightPlacement::Point(p) => light.as_mut().set_position(p),
+            LightPlacement::Directional(d) => light.as_mut().set_direction(d),
+        }
+        let [r, g, b] = self.color;
+        light.as_mut().set_color(r, g, b);
+        light.as_mut().set_enabled(self.enabled);
+        light.as_mut().set_shadow(self.shadow);
+        if self.distance_attenuation.is_some() {
+            light.set_distance_attenutation(self.distance_attenuation);
+        }
+    }
+}
+
 // Safety: I am 99% sure these are safe. That 1% is if citro3d does something weird I missed
 // which is not impossible
 unsafe impl Send for Light {}
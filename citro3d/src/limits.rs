@@ -0,0 +1,35 @@
+//! Hardware limits of the PICA200 GPU.
+//!
+//! These can be used to validate user-supplied data (e.g. textures loaded at
+//! runtime) before handing it to the GPU.
+
+/// The maximum width or height of a texture, in pixels.
+pub const MAX_TEXTURE_SIZE: u16 = 1024;
+
+/// The minimum width or height of a texture, in pixels.
+pub const MIN_TEXTURE_SIZE: u16 = 8;
+
+/// The number of texture units which can sample a [`Tex`](crate::texture::Tex).
+/// Unit 3 is reserved for procedural textures, see [`proctex`](crate::proctex).
+pub const MAX_TEXTURE_UNITS: usize = 3;
+
+/// The maximum number of vertex attributes in an [`attrib::Info`](crate::attrib::Info).
+pub const MAX_VERTEX_ATTRIBS: usize = 12;
+
+/// The maximum number of vertex buffers in a [`buffer::Info`](crate::buffer::Info).
+pub const MAX_BUFFER_CONFIGS: usize = crate::buffer::MAX_BUFFERS;
+
+/// The number of [`TexEnv`](crate::texenv::TexEnv) stages.
+pub const MAX_TEXENV_STAGES: usize = crate::texenv::TEXENV_COUNT;
+
+/// The number of hardware lights in a [`LightEnv`](crate::light::LightEnv).
+pub const MAX_LIGHTS: usize = 8;
+
+/// The number of float vector uniform registers per shader.
+pub const MAX_FLOAT_UNIFORMS: usize = 0x60;
+
+/// The number of integer vector uniform registers per shader.
+pub const MAX_INT_UNIFORMS: usize = 4;
+
+/// The number of boolean uniform registers per shader.
+pub const MAX_BOOL_UNIFORMS: usize = 16;
@@ -3,51 +3,110 @@
 // TODO: bench FFI calls into `inline statics` generated by bindgen, vs
 // reimplementing some of those calls. Many of them are pretty trivial impls
 
+mod angle;
 mod fvec;
 mod matrix;
 mod ops;
 mod projection;
+mod quat;
 
+pub use angle::Angle;
 pub use fvec::{FVec, FVec3, FVec4};
-pub use matrix::Matrix4;
+pub use matrix::{Matrix2, Matrix3, Matrix4};
 pub use projection::{
     AspectRatio, ClipPlanes, CoordinateOrientation, Orthographic, Perspective, Projection,
-    ScreenOrientation, StereoDisplacement,
+    ScreenOrientation, StereoDisplacement, StereoProjection,
 };
+pub use quat::{FQuat, Quat};
 
-/// A 4-vector of `u8`s.
+/// A 4-vector of `u8`s, used for integer uniforms ([`Uniform::Int`](crate::uniform::Uniform::Int)).
+///
+/// The PICA's integer uniform registers (`.ivec` in shaders) hold four 8-bit
+/// components, so each component ranges from `0` to `255`. Shaders can't do
+/// general arithmetic with them: they are only used to control `loop`
+/// instructions, where `x` is the iteration count minus one, `y` the initial
+/// value of the loop counter register `aL`, and `z` the amount `aL` is
+/// incremented by each iteration. `w` is unused.
 ///
 /// # Layout
 /// Uses the PICA layout of WZYX
+///
+/// # Example
+///
+/// ```
+/// # let _runner = test_runner::GdbRunner::default();
+/// # use citro3d::math::IVec;
+/// // Loop 16 times, with `aL` counting 0, 1, 2, ... 15
+/// let bones = IVec::new(15, 0, 1, 0);
+/// assert_eq!(IVec::from([15, 0, 1, 0]), bones);
+/// assert_eq!(IVec::from((15, 0, 1, 0)), bones);
+/// assert_eq!(<[u8; 4]>::from(bones), [15, 0, 1, 0]);
+/// ```
 #[doc(alias = "C3D_IVec")]
 #[repr(transparent)]
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct IVec(citro3d_sys::C3D_IVec);
 
 impl IVec {
+    /// Create a new vector from its components.
+    #[doc(alias = "IVec_Pack")]
     pub fn new(x: u8, y: u8, z: u8, w: u8) -> Self {
         Self(unsafe { citro3d_sys::IVec_Pack(x, y, z, w) })
     }
+    /// Get a reference to the underlying packed `citro3d` vector.
     pub fn as_raw(&self) -> &citro3d_sys::C3D_IVec {
         &self.0
     }
+    /// The vector's x component.
     pub fn x(self) -> u8 {
         self.0 as u8
     }
+    /// The vector's y component.
     pub fn y(self) -> u8 {
         (self.0 >> 8) as u8
     }
+    /// The vector's z component.
     pub fn z(self) -> u8 {
         (self.0 >> 16) as u8
     }
+    /// The vector's w component.
     pub fn w(self) -> u8 {
         (self.0 >> 24) as u8
     }
+    /// The vector's components, in XYZW order.
+    pub fn to_array(self) -> [u8; 4] {
+        [self.x(), self.y(), self.z(), self.w()]
+    }
+}
+
+impl From<[u8; 4]> for IVec {
+    fn from([x, y, z, w]: [u8; 4]) -> Self {
+        Self::new(x, y, z, w)
+    }
+}
+
+impl From<(u8, u8, u8, u8)> for IVec {
+    fn from((x, y, z, w): (u8, u8, u8, u8)) -> Self {
+        Self::new(x, y, z, w)
+    }
 }
 
-/// A quaternion, internally represented the same way as [`FVec`].
-#[doc(alias = "C3D_FQuat")]
-pub struct FQuat(citro3d_sys::C3D_FQuat);
+impl From<IVec> for [u8; 4] {
+    fn from(value: IVec) -> Self {
+        value.to_array()
+    }
+}
+
+impl std::fmt::Debug for IVec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IVec")
+            .field("x", &self.x())
+            .field("y", &self.y())
+            .field("z", &self.z())
+            .field("w", &self.w())
+            .finish()
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -61,4 +120,11 @@ mod tests {
         assert_eq!(iv.z(), 3);
         assert_eq!(iv.w(), 4);
     }
+
+    #[test]
+    fn ivec_array_round_trip() {
+        let iv = IVec::from([255, 0, 128, 7]);
+        assert_eq!(iv, IVec::new(255, 0, 128, 7));
+        assert_eq!(iv.to_array(), [255, 0, 128, 7]);
+    }
 }
@@ -0,0 +1,119 @@
+//! Units-aware angles.
+
+use std::f32::consts::TAU;
+use std::ops::{Add, Neg, Sub};
+
+/// An angle, used by the rotation and projection APIs in this module.
+///
+/// `libctru` and `citro3d` mostly take radians, but some parts of the PICA
+/// (e.g. `C3D_Angle`) use a normalized angle measured in turns. Constructing
+/// an `Angle` explicitly from one of these units avoids accidentally passing
+/// degrees to a function expecting radians.
+///
+/// # Example
+///
+/// ```
+/// # let _runner = test_runner::GdbRunner::default();
+/// # use citro3d::math::Angle;
+/// # use approx::assert_abs_diff_eq;
+/// let right = Angle::from_degrees(90.0);
+/// assert_abs_diff_eq!(right.radians(), std::f32::consts::FRAC_PI_2);
+/// assert_abs_diff_eq!(right.turns(), 0.25);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Angle {
+    radians: f32,
+}
+
+impl Angle {
+    /// The zero angle.
+    pub const ZERO: Self = Self { radians: 0.0 };
+
+    /// Create an angle measured in radians.
+    pub const fn from_radians(radians: f32) -> Self {
+        Self { radians }
+    }
+
+    /// Create an angle measured in degrees.
+    #[doc(alias = "C3D_AngleFromDegrees")]
+    pub fn from_degrees(degrees: f32) -> Self {
+        Self::from_radians(degrees.to_radians())
+    }
+
+    /// Create an angle measured in turns, i.e. full revolutions.
+    /// This is the normalized unit used by `C3D_Angle`.
+    #[doc(alias = "C3D_Angle")]
+    pub fn from_turns(turns: f32) -> Self {
+        Self::from_radians(turns * TAU)
+    }
+
+    /// The angle in radians.
+    pub const fn radians(self) -> f32 {
+        self.radians
+    }
+
+    /// The angle in degrees.
+    pub fn degrees(self) -> f32 {
+        self.radians.to_degrees()
+    }
+
+    /// The angle in turns, i.e. full revolutions.
+    pub fn turns(self) -> f32 {
+        self.radians / TAU
+    }
+}
+
+impl Add for Angle {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::from_radians(self.radians + rhs.radians)
+    }
+}
+
+impl Sub for Angle {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::from_radians(self.radians - rhs.radians)
+    }
+}
+
+impl Neg for Angle {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::from_radians(-self.radians)
+    }
+}
+
+#[cfg(feature = "approx")]
+#[doc(cfg(feature = "approx"))]
+impl approx::AbsDiffEq for Angle {
+    type Epsilon = f32;
+
+    fn default_epsilon() -> Self::Epsilon {
+        f32::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.radians.abs_diff_eq(&other.radians, epsilon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_abs_diff_eq!(Angle::from_degrees(180.0), Angle::from_radians(PI));
+        assert_abs_diff_eq!(Angle::from_turns(0.5), Angle::from_radians(PI));
+        assert_abs_diff_eq!(Angle::from_radians(PI).degrees(), 180.0);
+        assert_abs_diff_eq!(Angle::from_degrees(-90.0).turns(), -0.25);
+    }
+}
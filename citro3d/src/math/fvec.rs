@@ -46,6 +46,63 @@ impl<const N: usize> FVec<N> {
     pub fn z(self) -> f32 {
         unsafe { self.0.__bindgen_anon_1.z }
     }
+
+    /// Set the vector's `x` component.
+    pub fn set_x(&mut self, x: f32) {
+        let mut components = unsafe { self.0.__bindgen_anon_1 };
+        components.x = x;
+        self.0.__bindgen_anon_1 = components;
+    }
+
+    /// Set the vector's `y` component.
+    pub fn set_y(&mut self, y: f32) {
+        let mut components = unsafe { self.0.__bindgen_anon_1 };
+        components.y = y;
+        self.0.__bindgen_anon_1 = components;
+    }
+
+    /// Set the vector's `z` component.
+    pub fn set_z(&mut self, z: f32) {
+        let mut components = unsafe { self.0.__bindgen_anon_1 };
+        components.z = z;
+        self.0.__bindgen_anon_1 = components;
+    }
+
+    /// Return a copy of the vector with the `x` component replaced.
+    ///
+    /// # Example
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::FVec3;
+    /// # use approx::assert_abs_diff_eq;
+    /// let v = FVec3::new(1.0, 2.0, 3.0);
+    /// assert_abs_diff_eq!(v.with_x(5.0), FVec3::new(5.0, 2.0, 3.0));
+    /// ```
+    #[must_use]
+    pub fn with_x(mut self, x: f32) -> Self {
+        self.set_x(x);
+        self
+    }
+
+    /// Return a copy of the vector with the `y` component replaced.
+    #[must_use]
+    pub fn with_y(mut self, y: f32) -> Self {
+        self.set_y(y);
+        self
+    }
+
+    /// Return a copy of the vector with the `z` component replaced.
+    #[must_use]
+    pub fn with_z(mut self, z: f32) -> Self {
+        self.set_z(z);
+        self
+    }
+
+    /// The vector's `x` and `y` components, e.g. for the position of a point
+    /// on screen.
+    pub fn xy(self) -> [f32; 2] {
+        [self.x(), self.y()]
+    }
 }
 
 impl FVec4 {
@@ -55,6 +112,37 @@ impl FVec4 {
         unsafe { self.0.__bindgen_anon_1.w }
     }
 
+    /// Set the vector's `w` component.
+    ///
+    /// # Example
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::FVec4;
+    /// # use approx::assert_abs_diff_eq;
+    /// // Fade out a color uniform
+    /// let mut color = FVec4::new(1.0, 0.5, 0.0, 1.0);
+    /// color.set_w(0.25);
+    /// assert_abs_diff_eq!(color, FVec4::new(1.0, 0.5, 0.0, 0.25));
+    /// ```
+    pub fn set_w(&mut self, w: f32) {
+        let mut components = unsafe { self.0.__bindgen_anon_1 };
+        components.w = w;
+        self.0.__bindgen_anon_1 = components;
+    }
+
+    /// Return a copy of the vector with the `w` component replaced.
+    #[must_use]
+    pub fn with_w(mut self, w: f32) -> Self {
+        self.set_w(w);
+        self
+    }
+
+    /// The vector's `x`, `y` and `z` components. This is the same as
+    /// [`Self::truncate`].
+    pub fn xyz(self) -> FVec3 {
+        self.truncate()
+    }
+
     /// Create a new [`FVec4`] from its components.
     ///
     /// # Example
@@ -124,6 +212,7 @@ impl FVec4 {
     /// assert_abs_diff_eq!(v.magnitude(), 2.0);
     /// ```
     #[doc(alias = "FVec4_Magnitude")]
+    #[doc(alias = "length")]
     pub fn magnitude(self) -> f32 {
         unsafe { citro3d_sys::FVec4_Magnitude(self.0) }
     }
@@ -142,6 +231,20 @@ impl FVec4 {
     pub fn normalize(self) -> Self {
         Self(unsafe { citro3d_sys::FVec4_Normalize(self.0) })
     }
+
+    /// Drop the `w` component, e.g. to use a homogeneous point or direction as an [`FVec3`].
+    ///
+    /// # Example
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::{FVec3, FVec4};
+    /// # use approx::assert_abs_diff_eq;
+    /// let v = FVec4::new(1.0, 2.0, 3.0, 4.0);
+    /// assert_abs_diff_eq!(v.truncate(), FVec3::new(1.0, 2.0, 3.0));
+    /// ```
+    pub fn truncate(self) -> FVec3 {
+        FVec3::new(self.x(), self.y(), self.z())
+    }
 }
 
 impl FVec3 {
@@ -230,6 +333,7 @@ impl FVec3 {
     /// assert_abs_diff_eq!(v.magnitude(), 3.0);
     /// ```
     #[doc(alias = "FVec3_Magnitude")]
+    #[doc(alias = "length")]
     pub fn magnitude(self) -> f32 {
         unsafe { citro3d_sys::FVec3_Magnitude(self.0) }
     }
@@ -248,6 +352,67 @@ impl FVec3 {
     pub fn normalize(self) -> Self {
         Self(unsafe { citro3d_sys::FVec3_Normalize(self.0) })
     }
+
+    /// Create an [`FVec4`] from this vector and the given `w` component.
+    ///
+    /// Use `w = 1.0` for positions and `w = 0.0` for directions, so that
+    /// translations only affect the former.
+    ///
+    /// # Example
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::{FVec3, FVec4};
+    /// # use approx::assert_abs_diff_eq;
+    /// let v = FVec3::new(1.0, 2.0, 3.0);
+    /// assert_abs_diff_eq!(v.extend(1.0), FVec4::new(1.0, 2.0, 3.0, 1.0));
+    /// ```
+    pub fn extend(self, w: f32) -> FVec4 {
+        FVec4::new(self.x(), self.y(), self.z(), w)
+    }
+}
+
+impl From<(FVec3, f32)> for FVec4 {
+    fn from((xyz, w): (FVec3, f32)) -> Self {
+        xyz.extend(w)
+    }
+}
+
+impl From<FVec4> for FVec3 {
+    fn from(value: FVec4) -> Self {
+        value.truncate()
+    }
+}
+
+impl From<[f32; 4]> for FVec4 {
+    fn from([x, y, z, w]: [f32; 4]) -> Self {
+        Self::new(x, y, z, w)
+    }
+}
+impl From<(f32, f32, f32, f32)> for FVec4 {
+    fn from((x, y, z, w): (f32, f32, f32, f32)) -> Self {
+        Self::new(x, y, z, w)
+    }
+}
+impl From<FVec4> for [f32; 4] {
+    fn from(value: FVec4) -> Self {
+        [value.x(), value.y(), value.z(), value.w()]
+    }
+}
+
+impl From<[f32; 3]> for FVec3 {
+    fn from([x, y, z]: [f32; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+impl From<(f32, f32, f32)> for FVec3 {
+    fn from((x, y, z): (f32, f32, f32)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+impl From<FVec3> for [f32; 3] {
+    fn from(value: FVec3) -> Self {
+        [value.x(), value.y(), value.z()]
+    }
 }
 
 #[cfg(feature = "glam")]
@@ -290,6 +455,19 @@ mod tests {
         assert_abs_diff_eq!(&actual[..], &expected[..]);
     }
 
+    #[test]
+    fn set_components() {
+        let mut v = FVec4::splat(0.0);
+        v.set_x(1.0);
+        v.set_y(2.0);
+        v.set_z(3.0);
+        v.set_w(4.0);
+        assert_abs_diff_eq!(v, FVec4::new(1.0, 2.0, 3.0, 4.0));
+        assert_abs_diff_eq!(v.with_y(0.0), FVec4::new(1.0, 0.0, 3.0, 4.0));
+        assert_abs_diff_eq!(v.xyz(), FVec3::new(1.0, 2.0, 3.0));
+        assert_eq!(v.xy(), [1.0, 2.0]);
+    }
+
     #[test]
     fn fvec3() {
         let v = FVec3::new(1.0, 2.0, 3.0);
@@ -297,4 +475,25 @@ mod tests {
         let expected = [1.0, 2.0, 3.0];
         assert_abs_diff_eq!(&actual[..], &expected[..]);
     }
+
+    #[test]
+    fn fvec3_fvec4_conversions() {
+        let v = FVec3::new(1.0, 2.0, 3.0);
+        let extended = FVec4::from((v, 0.0));
+        assert_abs_diff_eq!(extended, FVec4::new(1.0, 2.0, 3.0, 0.0));
+        assert_abs_diff_eq!(FVec3::from(extended), v);
+    }
+
+    #[test]
+    fn array_tuple_conversions() {
+        let v = FVec4::new(1.0, 2.0, 3.0, 4.0);
+        assert_abs_diff_eq!(FVec4::from([1.0, 2.0, 3.0, 4.0]), v);
+        assert_abs_diff_eq!(FVec4::from((1.0, 2.0, 3.0, 4.0)), v);
+        assert_eq!(<[f32; 4]>::from(v), [1.0, 2.0, 3.0, 4.0]);
+
+        let v = FVec3::new(1.0, 2.0, 3.0);
+        assert_abs_diff_eq!(FVec3::from([1.0, 2.0, 3.0]), v);
+        assert_abs_diff_eq!(FVec3::from((1.0, 2.0, 3.0)), v);
+        assert_eq!(<[f32; 3]>::from(v), [1.0, 2.0, 3.0]);
+    }
 }
@@ -1,13 +1,22 @@
 use std::mem::MaybeUninit;
+use std::ops::{Index, Range};
 
-use super::{CoordinateOrientation, FVec3, FVec4};
+use super::{
+    Angle, AspectRatio, ClipPlanes, CoordinateOrientation, FVec3, FVec4, Projection,
+    ScreenOrientation, StereoDisplacement, StereoProjection,
+};
 
 /// A 4x4 row-major matrix of `f32`s.
 ///
 /// # Layout details
-/// Rows are actually stored as WZYX in memory. There are helper functions
-/// for accessing the rows in XYZW form. The `Debug` implementation prints
-/// the shows in WZYX form
+/// Rows are actually stored as WZYX in memory, i.e. each row's components are
+/// reversed, since that is the order the PICA expects when the matrix is
+/// uploaded as a uniform. Element `(row, col)` is therefore stored at
+/// `m[row * 4 + (3 - col)]` of the raw [`citro3d_sys::C3D_Mtx`].
+///
+/// [`Self::row`], [`Self::get`] and indexing with `matrix[(row, col)]` hide this
+/// and present a conventional row/column view, as does [`Self::rows_xyzw`].
+/// The `Debug` implementation prints the rows in WZYX form.
 ///
 /// It is also guaranteed to have the same layout as [`citro3d_sys::C3D_Mtx`]
 #[doc(alias = "C3D_Mtx")]
@@ -30,6 +39,46 @@ impl Matrix4 {
             r: unsafe { core::mem::transmute::<_, [citro3d_sys::C3D_FVec; 4]>(rows) },
         })
     }
+
+    /// Construct a matrix from its rows, each in conventional XYZW order.
+    /// This is the inverse of [`Self::rows_xyzw`].
+    pub fn from_rows_xyzw(rows: [[f32; 4]; 4]) -> Self {
+        Self::from_rows(rows.map(|[x, y, z, w]| FVec4::new(x, y, z, w)))
+    }
+
+    /// Construct a matrix from 16 floats in row-major order, i.e. element
+    /// `(row, col)` is `cells[row * 4 + col]`. The translation of an affine
+    /// transform is at indices 3, 7 and 11.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::Matrix4;
+    /// #[rustfmt::skip]
+    /// let cells = [
+    ///     1.0, 0.0, 0.0, 5.0,
+    ///     0.0, 1.0, 0.0, 6.0,
+    ///     0.0, 0.0, 1.0, 7.0,
+    ///     0.0, 0.0, 0.0, 1.0,
+    /// ];
+    /// assert_eq!(Matrix4::from_row_major(&cells), Matrix4::translation(5.0, 6.0, 7.0));
+    /// ```
+    pub fn from_row_major(cells: &[f32; 16]) -> Self {
+        Self::from_rows_xyzw(std::array::from_fn(|row| {
+            std::array::from_fn(|col| cells[row * 4 + col])
+        }))
+    }
+
+    /// Construct a matrix from 16 floats in column-major order (as used by
+    /// e.g. OpenGL and glTF), i.e. element `(row, col)` is `cells[col * 4 + row]`.
+    /// The translation of an affine transform is at indices 12, 13 and 14.
+    pub fn from_column_major(cells: &[f32; 16]) -> Self {
+        Self::from_rows_xyzw(std::array::from_fn(|row| {
+            std::array::from_fn(|col| cells[col * 4 + row])
+        }))
+    }
+
     /// Create a new matrix from a raw citro3d_sys one
     pub fn from_raw(value: citro3d_sys::C3D_Mtx) -> Self {
         Self(value)
@@ -64,6 +113,46 @@ impl Matrix4 {
         // Safety: FVec has same layout as citro3d_sys::C3D_FVec which is a union with [f32; 4] as one variant
         unsafe { std::mem::transmute::<_, [[f32; 4]; 4]>(rows) }
     }
+
+    /// Get row `i` of the matrix.
+    ///
+    /// # Panics
+    ///
+    /// If `i` is not less than 4.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::{FVec4, Matrix4};
+    /// # use approx::assert_abs_diff_eq;
+    /// let mtx = Matrix4::translation(1.0, 2.0, 3.0);
+    /// assert_abs_diff_eq!(mtx.row(0), FVec4::new(1.0, 0.0, 0.0, 1.0));
+    /// ```
+    pub fn row(&self, i: usize) -> FVec4 {
+        self.rows_wzyx()[i]
+    }
+
+    /// Get the element at the given row and column of the matrix.
+    /// This is equivalent to `self[(row, col)]`.
+    ///
+    /// # Panics
+    ///
+    /// If `row` or `col` is not less than 4.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::Matrix4;
+    /// let mtx = Matrix4::translation(1.0, 2.0, 3.0);
+    /// assert_eq!(mtx.get(1, 3), 2.0);
+    /// assert_eq!(mtx[(2, 3)], 3.0);
+    /// ```
+    pub fn get(&self, row: usize, col: usize) -> f32 {
+        self[(row, col)]
+    }
+
     /// Construct the zero matrix.
     #[doc(alias = "Mtx_Zeros")]
     pub fn zero() -> Self {
@@ -93,7 +182,8 @@ impl Matrix4 {
     // opposite order, or an enum arg for these APIs or something.
 
     /// Translate a transformation matrix by the given amounts in the X, Y, and Z
-    /// directions.
+    /// directions. The translation is applied after any existing transformations
+    /// in the matrix, i.e. `self = T * self`.
     #[doc(alias = "Mtx_Translate")]
     pub fn translate(&mut self, x: f32, y: f32, z: f32) {
         unsafe { citro3d_sys::Mtx_Translate(self.as_raw_mut(), x, y, z, false) }
@@ -107,26 +197,89 @@ impl Matrix4 {
 
     /// Rotate a transformation matrix by the given angle around the given axis.
     #[doc(alias = "Mtx_Rotate")]
-    pub fn rotate(&mut self, axis: FVec3, angle: f32) {
-        unsafe { citro3d_sys::Mtx_Rotate(self.as_raw_mut(), axis.0, angle, false) }
+    pub fn rotate(&mut self, axis: FVec3, angle: Angle) {
+        unsafe { citro3d_sys::Mtx_Rotate(self.as_raw_mut(), axis.0, angle.radians(), false) }
     }
 
     /// Rotate a transformation matrix by the given angle around the X axis.
     #[doc(alias = "Mtx_RotateX")]
-    pub fn rotate_x(&mut self, angle: f32) {
-        unsafe { citro3d_sys::Mtx_RotateX(self.as_raw_mut(), angle, false) }
+    pub fn rotate_x(&mut self, angle: Angle) {
+        unsafe { citro3d_sys::Mtx_RotateX(self.as_raw_mut(), angle.radians(), false) }
     }
 
     /// Rotate a transformation matrix by the given angle around the Y axis.
     #[doc(alias = "Mtx_RotateY")]
-    pub fn rotate_y(&mut self, angle: f32) {
-        unsafe { citro3d_sys::Mtx_RotateY(self.as_raw_mut(), angle, false) }
+    pub fn rotate_y(&mut self, angle: Angle) {
+        unsafe { citro3d_sys::Mtx_RotateY(self.as_raw_mut(), angle.radians(), false) }
     }
 
     /// Rotate a transformation matrix by the given angle around the Z axis.
     #[doc(alias = "Mtx_RotateZ")]
-    pub fn rotate_z(&mut self, angle: f32) {
-        unsafe { citro3d_sys::Mtx_RotateZ(self.as_raw_mut(), angle, false) }
+    pub fn rotate_z(&mut self, angle: Angle) {
+        unsafe { citro3d_sys::Mtx_RotateZ(self.as_raw_mut(), angle.radians(), false) }
+    }
+
+    /// Construct a transformation matrix translating by the given amounts in the
+    /// X, Y, and Z directions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::{FVec4, Matrix4};
+    /// # use approx::assert_abs_diff_eq;
+    /// let mtx = Matrix4::translation(1.0, 2.0, 3.0);
+    /// let origin = FVec4::new(0.0, 0.0, 0.0, 1.0);
+    /// assert_abs_diff_eq!(&mtx * origin, FVec4::new(1.0, 2.0, 3.0, 1.0));
+    /// ```
+    #[doc(alias = "Mtx_Translate")]
+    pub fn translation(x: f32, y: f32, z: f32) -> Self {
+        let mut out = Self::identity();
+        out.translate(x, y, z);
+        out
+    }
+
+    /// Construct a transformation matrix scaling by the given amounts in the
+    /// X, Y, and Z directions.
+    ///
+    /// The in-place equivalent is [`Self::scale`].
+    #[doc(alias = "Mtx_Scale")]
+    pub fn scaling(x: f32, y: f32, z: f32) -> Self {
+        let mut out = Self::identity();
+        out.scale(x, y, z);
+        out
+    }
+
+    /// Construct a transformation matrix rotating by the given angle around the given axis.
+    #[doc(alias = "Mtx_Rotate")]
+    pub fn rotation(axis: FVec3, angle: Angle) -> Self {
+        let mut out = Self::identity();
+        out.rotate(axis, angle);
+        out
+    }
+
+    /// Construct a transformation matrix rotating by the given angle around the X axis.
+    #[doc(alias = "Mtx_RotateX")]
+    pub fn rotation_x(angle: Angle) -> Self {
+        let mut out = Self::identity();
+        out.rotate_x(angle);
+        out
+    }
+
+    /// Construct a transformation matrix rotating by the given angle around the Y axis.
+    #[doc(alias = "Mtx_RotateY")]
+    pub fn rotation_y(angle: Angle) -> Self {
+        let mut out = Self::identity();
+        out.rotate_y(angle);
+        out
+    }
+
+    /// Construct a transformation matrix rotating by the given angle around the Z axis.
+    #[doc(alias = "Mtx_RotateZ")]
+    pub fn rotation_z(angle: Angle) -> Self {
+        let mut out = Self::identity();
+        out.rotate_z(angle);
+        out
     }
 
     /// Find the inverse of the matrix.
@@ -144,6 +297,38 @@ impl Matrix4 {
         }
     }
 
+    /// Compute the matrix used to transform normal vectors for this (model)
+    /// transformation matrix, i.e. the inverse-transpose of its upper-left 3x3
+    /// portion. The translation part of the result is zeroed.
+    ///
+    /// Returns [`None`] if the matrix has no inverse.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::Matrix4;
+    /// # use approx::assert_abs_diff_eq;
+    /// let mut model = Matrix4::scaling(2.0, 2.0, 2.0);
+    /// model.translate(5.0, 0.0, 0.0);
+    ///
+    /// let normal = model.normal_matrix().unwrap();
+    /// assert_abs_diff_eq!(normal, Matrix4::diagonal(0.5, 0.5, 0.5, 1.0));
+    ///
+    /// assert!(Matrix4::zero().normal_matrix().is_none());
+    /// ```
+    pub fn normal_matrix(&self) -> Option<Self> {
+        let [r0, r1, r2, _] = self.rows_xyzw();
+        let upper = Self::from_rows([
+            FVec4::new(r0[0], r0[1], r0[2], 0.0),
+            FVec4::new(r1[0], r1[1], r1[2], 0.0),
+            FVec4::new(r2[0], r2[1], r2[2], 0.0),
+            FVec4::new(0.0, 0.0, 0.0, 1.0),
+        ]);
+
+        upper.inverse().ok().map(Self::transpose)
+    }
+
     /// Construct the identity matrix.
     #[doc(alias = "Mtx_Identity")]
     pub fn identity() -> Self {
@@ -154,6 +339,32 @@ impl Matrix4 {
         }
     }
 
+    /// Check whether every element of this matrix is within `epsilon` of the
+    /// corresponding element of `other`.
+    ///
+    /// Computations on the 3DS (and the projections built by citro3d) are
+    /// subject to rounding errors, so this is usually more useful than `==`,
+    /// which compares the elements exactly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::{Angle, Matrix4};
+    /// let mut m = Matrix4::default();
+    /// m.rotate_z(Angle::from_degrees(90.0));
+    /// m.rotate_z(Angle::from_degrees(-90.0));
+    ///
+    /// assert!(m.approx_eq(&Matrix4::identity(), 1e-6));
+    /// ```
+    pub fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.rows_xyzw()
+            .iter()
+            .flatten()
+            .zip(other.rows_xyzw().iter().flatten())
+            .all(|(l, r)| (l - r).abs() <= epsilon)
+    }
+
     /// Construct a 4x4 matrix with the given values on the diagonal.
     #[doc(alias = "Mtx_Diagonal")]
     pub fn diagonal(x: f32, y: f32, z: f32, w: f32) -> Self {
@@ -164,27 +375,176 @@ impl Matrix4 {
         }
     }
 
-    /// Construct a 3D transformation matrix for a camera, given its position,
+    /// Construct a 3D transformation (view) matrix for a camera, given its position,
     /// target, and upward direction.
+    ///
+    /// Like all [`Matrix4`]s, the result is row-major, which is the layout
+    /// expected when binding it as a uniform (e.g. `.fvec view[4]` in a shader),
+    /// so it can be bound directly without transposing.
+    ///
+    /// With the `glam` feature enabled, the vectors may also be given as [`glam::Vec3`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::{CoordinateOrientation, FVec3, Matrix4};
+    /// let view = Matrix4::looking_at(
+    ///     FVec3::new(1.0, 1.0, 1.0),
+    ///     FVec3::splat(0.0),
+    ///     FVec3::new(0.0, 1.0, 0.0),
+    ///     CoordinateOrientation::RightHanded,
+    /// );
+    /// ```
     #[doc(alias = "Mtx_LookAt")]
+    #[doc(alias = "look_at")]
     pub fn looking_at(
-        camera_position: FVec3,
-        camera_target: FVec3,
-        camera_up: FVec3,
+        camera_position: impl Into<FVec3>,
+        camera_target: impl Into<FVec3>,
+        camera_up: impl Into<FVec3>,
         coordinates: CoordinateOrientation,
     ) -> Self {
         let mut out = MaybeUninit::uninit();
         unsafe {
             citro3d_sys::Mtx_LookAt(
                 out.as_mut_ptr(),
-                camera_position.0,
-                camera_target.0,
-                camera_up.0,
+                camera_position.into().0,
+                camera_target.into().0,
+                camera_up.into().0,
                 coordinates.is_left_handed(),
             );
             Self::from_raw(out.assume_init())
         }
     }
+
+    /// Construct a perspective projection matrix, rotated to account for the
+    /// 3DS screen orientation (see [`ScreenOrientation`](super::ScreenOrientation)).
+    ///
+    /// This is a shorthand for [`Projection::perspective`] with the given
+    /// [`CoordinateOrientation`]; use [`Projection`] directly for more options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::{Angle, AspectRatio, ClipPlanes, CoordinateOrientation, Matrix4};
+    /// let mtx = Matrix4::perspective(
+    ///     Angle::from_degrees(40.0),
+    ///     AspectRatio::TopScreen,
+    ///     ClipPlanes {
+    ///         near: 0.01,
+    ///         far: 100.0,
+    ///     },
+    ///     CoordinateOrientation::RightHanded,
+    /// );
+    /// ```
+    #[doc(alias = "Mtx_PerspTilt")]
+    pub fn perspective(
+        vertical_fov: Angle,
+        aspect_ratio: AspectRatio,
+        clip_planes: ClipPlanes,
+        coordinates: CoordinateOrientation,
+    ) -> Self {
+        Projection::perspective(vertical_fov, aspect_ratio, clip_planes)
+            .coordinates(coordinates)
+            .into()
+    }
+
+    /// Construct perspective projection matrices for both eyes, for stereoscopic
+    /// rendering on the top screen. The matrices are rotated to account for the
+    /// 3DS screen orientation.
+    ///
+    /// `interocular_distance` is the distance between the two eyes, and
+    /// `screen_depth` the distance at which objects appear at the depth of the
+    /// screen. To respect the 3D slider, scale `interocular_distance` by
+    /// [`ctru::os::current_3d_slider_state`]. See [`StereoDisplacement`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::{Angle, AspectRatio, ClipPlanes, CoordinateOrientation, Matrix4};
+    /// let slider = ctru::os::current_3d_slider_state();
+    /// let stereo = Matrix4::perspective_stereo(
+    ///     Angle::from_degrees(40.0),
+    ///     AspectRatio::TopScreen,
+    ///     ClipPlanes {
+    ///         near: 0.01,
+    ///         far: 100.0,
+    ///     },
+    ///     slider / 2.0,
+    ///     2.0,
+    ///     CoordinateOrientation::RightHanded,
+    /// );
+    /// # let _ = (stereo.left, stereo.right);
+    /// ```
+    #[doc(alias = "Mtx_PerspStereoTilt")]
+    pub fn perspective_stereo(
+        vertical_fov: Angle,
+        aspect_ratio: AspectRatio,
+        clip_planes: ClipPlanes,
+        interocular_distance: f32,
+        screen_depth: f32,
+        coordinates: CoordinateOrientation,
+    ) -> StereoProjection {
+        let (left_eye, right_eye) = StereoDisplacement::new(interocular_distance, screen_depth);
+        let (left, right) = Projection::perspective(vertical_fov, aspect_ratio, clip_planes)
+            .coordinates(coordinates)
+            .stereo_matrices(left_eye, right_eye);
+
+        StereoProjection { left, right }
+    }
+
+    /// Construct an orthographic projection matrix, rotated to account for the
+    /// 3DS screen orientation (see [`ScreenOrientation`]).
+    ///
+    /// This is a shorthand for [`Projection::orthographic`] with the given
+    /// [`CoordinateOrientation`]; use [`Projection`] directly for more options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::{ClipPlanes, CoordinateOrientation, Matrix4};
+    /// let mtx = Matrix4::orthographic(
+    ///     0.0..400.0,
+    ///     0.0..240.0,
+    ///     ClipPlanes {
+    ///         near: 0.0,
+    ///         far: 1.0,
+    ///     },
+    ///     CoordinateOrientation::RightHanded,
+    /// );
+    /// ```
+    #[doc(alias = "Mtx_OrthoTilt")]
+    pub fn orthographic(
+        clip_planes_x: Range<f32>,
+        clip_planes_y: Range<f32>,
+        clip_planes_z: ClipPlanes,
+        coordinates: CoordinateOrientation,
+    ) -> Self {
+        Projection::orthographic(clip_planes_x, clip_planes_y, clip_planes_z)
+            .coordinates(coordinates)
+            .into()
+    }
+
+    /// Construct an orthographic projection matrix without any extra rotation.
+    ///
+    /// This is useful for render targets that are not displayed directly on
+    /// one of the screens (e.g. when rendering to a texture), since their
+    /// contents are not rotated. Otherwise, this is the same as [`Self::orthographic`].
+    #[doc(alias = "Mtx_Ortho")]
+    pub fn orthographic_no_tilt(
+        clip_planes_x: Range<f32>,
+        clip_planes_y: Range<f32>,
+        clip_planes_z: ClipPlanes,
+        coordinates: CoordinateOrientation,
+    ) -> Self {
+        Projection::orthographic(clip_planes_x, clip_planes_y, clip_planes_z)
+            .coordinates(coordinates)
+            .screen(ScreenOrientation::None)
+            .into()
+    }
 }
 
 impl core::fmt::Debug for Matrix4 {
@@ -192,6 +552,8 @@ impl core::fmt::Debug for Matrix4 {
         f.debug_tuple("Matrix4").field(&self.rows_wzyx()).finish()
     }
 }
+/// Compares the elements exactly. See [`Matrix4::approx_eq`] to allow for
+/// rounding errors.
 impl PartialEq<Matrix4> for Matrix4 {
     fn eq(&self, other: &Matrix4) -> bool {
         self.rows_wzyx() == other.rows_wzyx()
@@ -199,6 +561,217 @@ impl PartialEq<Matrix4> for Matrix4 {
 }
 impl Eq for Matrix4 {}
 
+/// The identity matrix.
+impl Default for Matrix4 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// A 3x3 row-major matrix of `f32`s.
+///
+/// This is useful for uniforms that don't need a full 4x4 matrix, such as a
+/// normal matrix, since it only takes up three uniform registers.
+///
+/// # Layout details
+/// Internally, this is stored as a [`citro3d_sys::C3D_Mtx`] with the fourth
+/// row and column set to zero, so the same `Mtx_*` functions can be used to
+/// operate on it.
+#[doc(alias = "C3D_Mtx")]
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Matrix3(citro3d_sys::C3D_Mtx);
+
+impl Matrix3 {
+    /// Construct a Matrix3 from its rows.
+    pub fn from_rows(rows: [FVec3; 3]) -> Self {
+        let [r0, r1, r2] = rows;
+        let row = |r: FVec3| FVec4::new(r.x(), r.y(), r.z(), 0.0);
+
+        Self(Matrix4::from_rows([row(r0), row(r1), row(r2), FVec4::splat(0.0)]).into_raw())
+    }
+
+    pub fn as_raw(&self) -> &citro3d_sys::C3D_Mtx {
+        &self.0
+    }
+
+    pub(crate) fn as_raw_mut(&mut self) -> &mut citro3d_sys::C3D_Mtx {
+        &mut self.0
+    }
+
+    /// Get the rows in raw (WZYX) form. The W component of each row is zero.
+    pub fn rows_wzyx(self) -> [FVec4; 3] {
+        let [r0, r1, r2, _] = Matrix4::from_raw(self.0).rows_wzyx();
+        [r0, r1, r2]
+    }
+
+    /// Get the rows in XYZ form.
+    pub fn rows_xyz(self) -> [[f32; 3]; 3] {
+        self.rows_wzyx().map(|r| [r.x(), r.y(), r.z()])
+    }
+
+    /// Construct the zero matrix.
+    #[doc(alias = "Mtx_Zeros")]
+    pub fn zero() -> Self {
+        Self(Matrix4::zero().into_raw())
+    }
+
+    /// Construct the identity matrix.
+    pub fn identity() -> Self {
+        Self::diagonal(1.0, 1.0, 1.0)
+    }
+
+    /// Construct a 3x3 matrix with the given values on the diagonal.
+    #[doc(alias = "Mtx_Diagonal")]
+    pub fn diagonal(x: f32, y: f32, z: f32) -> Self {
+        Self(Matrix4::diagonal(x, y, z, 0.0).into_raw())
+    }
+
+    /// Transpose the matrix, swapping rows and columns.
+    #[doc(alias = "Mtx_Transpose")]
+    pub fn transpose(mut self) -> Self {
+        unsafe {
+            citro3d_sys::Mtx_Transpose(self.as_raw_mut());
+        }
+        self
+    }
+}
+
+impl From<Matrix4> for Matrix3 {
+    /// Take the upper-left 3x3 portion of a [`Matrix4`]. This can be used
+    /// together with [`Matrix4::normal_matrix`] to bind a normal matrix
+    /// using only three uniform registers.
+    fn from(mat: Matrix4) -> Self {
+        let [r0, r1, r2, _] = mat.rows_xyzw();
+        let row = |r: [f32; 4]| FVec3::new(r[0], r[1], r[2]);
+        Self::from_rows([row(r0), row(r1), row(r2)])
+    }
+}
+
+impl core::fmt::Debug for Matrix3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Matrix3").field(&self.rows_xyz()).finish()
+    }
+}
+impl PartialEq<Matrix3> for Matrix3 {
+    fn eq(&self, other: &Matrix3) -> bool {
+        self.rows_xyz() == other.rows_xyz()
+    }
+}
+impl Eq for Matrix3 {}
+
+/// The identity matrix.
+impl Default for Matrix3 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// A 2x2 row-major matrix of `f32`s.
+///
+/// This only takes up two uniform registers when bound to a shader.
+///
+/// # Layout details
+/// Like [`Matrix3`], this is stored as a [`citro3d_sys::C3D_Mtx`] with the
+/// unused rows and columns set to zero.
+#[doc(alias = "C3D_Mtx")]
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Matrix2(citro3d_sys::C3D_Mtx);
+
+impl Matrix2 {
+    /// Construct a Matrix2 from its rows, in XY form.
+    pub fn from_rows(rows: [[f32; 2]; 2]) -> Self {
+        let [[a, b], [c, d]] = rows;
+        Self(
+            Matrix4::from_rows([
+                FVec4::new(a, b, 0.0, 0.0),
+                FVec4::new(c, d, 0.0, 0.0),
+                FVec4::splat(0.0),
+                FVec4::splat(0.0),
+            ])
+            .into_raw(),
+        )
+    }
+
+    pub fn as_raw(&self) -> &citro3d_sys::C3D_Mtx {
+        &self.0
+    }
+
+    pub(crate) fn as_raw_mut(&mut self) -> &mut citro3d_sys::C3D_Mtx {
+        &mut self.0
+    }
+
+    /// Get the rows in raw (WZYX) form. The Z and W components of each row are zero.
+    pub fn rows_wzyx(self) -> [FVec4; 2] {
+        let [r0, r1, _, _] = Matrix4::from_raw(self.0).rows_wzyx();
+        [r0, r1]
+    }
+
+    /// Get the rows in XY form.
+    pub fn rows_xy(self) -> [[f32; 2]; 2] {
+        self.rows_wzyx().map(|r| [r.x(), r.y()])
+    }
+
+    /// Construct the zero matrix.
+    #[doc(alias = "Mtx_Zeros")]
+    pub fn zero() -> Self {
+        Self(Matrix4::zero().into_raw())
+    }
+
+    /// Construct the identity matrix.
+    pub fn identity() -> Self {
+        Self::diagonal(1.0, 1.0)
+    }
+
+    /// Construct a 2x2 matrix with the given values on the diagonal.
+    #[doc(alias = "Mtx_Diagonal")]
+    pub fn diagonal(x: f32, y: f32) -> Self {
+        Self(Matrix4::diagonal(x, y, 0.0, 0.0).into_raw())
+    }
+
+    /// Transpose the matrix, swapping rows and columns.
+    #[doc(alias = "Mtx_Transpose")]
+    pub fn transpose(mut self) -> Self {
+        unsafe {
+            citro3d_sys::Mtx_Transpose(self.as_raw_mut());
+        }
+        self
+    }
+}
+
+impl core::fmt::Debug for Matrix2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Matrix2").field(&self.rows_xy()).finish()
+    }
+}
+impl PartialEq<Matrix2> for Matrix2 {
+    fn eq(&self, other: &Matrix2) -> bool {
+        self.rows_xy() == other.rows_xy()
+    }
+}
+impl Eq for Matrix2 {}
+
+/// The identity matrix.
+impl Default for Matrix2 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Index<(usize, usize)> for Matrix4 {
+    type Output = f32;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        assert!(
+            row < 4 && col < 4,
+            "matrix index ({row}, {col}) out of bounds for 4x4 matrix"
+        );
+        // Safety: all variants of the union are plain f32 data
+        unsafe { &self.0.m[row * 4 + (3 - col)] }
+    }
+}
+
 #[cfg(feature = "glam")]
 impl From<glam::Mat4> for Matrix4 {
     fn from(mat: glam::Mat4) -> Self {
@@ -206,9 +779,97 @@ impl From<glam::Mat4> for Matrix4 {
     }
 }
 
+/// Read back a matrix (e.g. one built with [`Matrix4::perspective`]) for use on
+/// the CPU side, such as frustum culling. Element `(row, col)` of the matrix
+/// is the same in both representations, so converting a [`glam::Mat4`] to a
+/// [`Matrix4`] and back yields the original matrix.
 #[cfg(feature = "glam")]
 impl From<Matrix4> for glam::Mat4 {
     fn from(mat: Matrix4) -> Self {
         glam::Mat4::from_cols_array_2d(&mat.rows_xyzw()).transpose()
     }
 }
+
+#[cfg(feature = "glam")]
+impl From<&Matrix4> for glam::Mat4 {
+    fn from(mat: &Matrix4) -> Self {
+        (*mat).into()
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::Quat> for Matrix4 {
+    fn from(quat: glam::Quat) -> Self {
+        super::FQuat::from(quat).to_matrix()
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::Mat3> for Matrix3 {
+    fn from(mat: glam::Mat3) -> Self {
+        Matrix3::from_rows(core::array::from_fn(|i| mat.row(i).into()))
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<Matrix3> for glam::Mat3 {
+    fn from(mat: Matrix3) -> Self {
+        glam::Mat3::from_cols_array_2d(&mat.rows_xyz()).transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_row_major_round_trip() {
+        let cells: [f32; 16] = std::array::from_fn(|i| i as f32);
+
+        let row_major = Matrix4::from_row_major(&cells);
+        let col_major = Matrix4::from_column_major(&cells);
+        for row in 0..4 {
+            for col in 0..4 {
+                assert_eq!(row_major[(row, col)], cells[row * 4 + col]);
+                assert_eq!(col_major[(row, col)], cells[col * 4 + row]);
+            }
+        }
+
+        assert_eq!(col_major, row_major.transpose());
+        assert_eq!(Matrix4::from_rows_xyzw(row_major.rows_xyzw()), row_major);
+    }
+
+    #[test]
+    fn default_is_identity() {
+        assert_eq!(Matrix4::default(), Matrix4::identity());
+        assert_eq!(Matrix3::default(), Matrix3::identity());
+        assert_eq!(Matrix2::default(), Matrix2::identity());
+
+        let nudged = Matrix4::diagonal(1.0, 1.0, 1.0, 1.0 + 1e-4);
+        assert!(nudged.approx_eq(&Matrix4::default(), 1e-3));
+        assert!(!nudged.approx_eq(&Matrix4::default(), 1e-5));
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn from_glam_quat() {
+        use approx::assert_abs_diff_eq;
+
+        let angle = Angle::from_degrees(30.0);
+        assert_abs_diff_eq!(
+            Matrix4::from(glam::Quat::from_rotation_x(angle.radians())),
+            Matrix4::rotation_x(angle),
+            epsilon = 1e-6
+        );
+        assert_abs_diff_eq!(
+            Matrix4::from(glam::Quat::from_rotation_y(angle.radians())),
+            Matrix4::rotation_y(angle),
+            epsilon = 1e-6
+        );
+        assert_abs_diff_eq!(
+            Matrix4::from(glam::Quat::from_rotation_z(angle.radians())),
+            Matrix4::rotation_z(angle),
+            epsilon = 1e-6
+        );
+    }
+}
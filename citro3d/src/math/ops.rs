@@ -1,10 +1,10 @@
 use std::mem::MaybeUninit;
-use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 #[cfg(feature = "approx")]
 use approx::AbsDiffEq;
 
-use super::{FVec, FVec3, FVec4, Matrix4};
+use super::{FVec, FVec3, FVec4, Matrix2, Matrix3, Matrix4};
 
 // region: FVec4 math operators
 
@@ -97,6 +97,53 @@ where
     }
 }
 
+impl<const N: usize> Mul<FVec<N>> for f32
+where
+    FVec<N>: Mul<f32>,
+{
+    type Output = <FVec<N> as Mul<f32>>::Output;
+
+    fn mul(self, rhs: FVec<N>) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl<const N: usize> AddAssign for FVec<N>
+where
+    FVec<N>: Add<Output = Self>,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> SubAssign for FVec<N>
+where
+    FVec<N>: Sub<Output = Self>,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize> MulAssign<f32> for FVec<N>
+where
+    FVec<N>: Mul<f32, Output = Self>,
+{
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const N: usize> DivAssign<f32> for FVec<N>
+where
+    FVec<N>: Div<f32, Output = Self>,
+{
+    fn div_assign(&mut self, rhs: f32) {
+        *self = *self / rhs;
+    }
+}
+
 impl<const N: usize> PartialEq for FVec<N> {
     fn eq(&self, other: &Self) -> bool {
         let range = (4 - N)..;
@@ -172,6 +219,30 @@ impl Mul<Matrix4> for &Matrix4 {
     }
 }
 
+impl Mul<&Matrix4> for &Matrix4 {
+    type Output = Matrix4;
+
+    fn mul(self, rhs: &Matrix4) -> Self::Output {
+        *self * *rhs
+    }
+}
+
+impl Add<&Matrix4> for &Matrix4 {
+    type Output = Matrix4;
+
+    fn add(self, rhs: &Matrix4) -> Self::Output {
+        *self + *rhs
+    }
+}
+
+impl Sub<&Matrix4> for &Matrix4 {
+    type Output = Matrix4;
+
+    fn sub(self, rhs: &Matrix4) -> Self::Output {
+        *self - *rhs
+    }
+}
+
 impl Mul<FVec4> for &Matrix4 {
     type Output = FVec4;
 
@@ -190,6 +261,103 @@ impl Mul<FVec3> for &Matrix4 {
     }
 }
 
+impl Mul<FVec3> for &Matrix3 {
+    type Output = FVec3;
+
+    #[doc(alias = "Mtx_MultiplyFVec3")]
+    fn mul(self, rhs: FVec3) -> Self::Output {
+        FVec(unsafe { citro3d_sys::Mtx_MultiplyFVec3(self.as_raw(), rhs.0) })
+    }
+}
+
+/// [`Matrix3`] and [`Matrix2`] are stored as a zero-padded `C3D_Mtx`, so the
+/// regular `Mtx_*` functions work on them as-is.
+macro_rules! impl_padded_matrix_ops {
+    ($($mtx:ident),*) => {$(
+        impl Add<$mtx> for $mtx {
+            type Output = $mtx;
+
+            #[doc(alias = "Mtx_Add")]
+            fn add(self, rhs: $mtx) -> Self::Output {
+                let mut out = self;
+                unsafe {
+                    citro3d_sys::Mtx_Add(out.as_raw_mut(), self.as_raw(), rhs.as_raw());
+                }
+                out
+            }
+        }
+
+        impl Sub<$mtx> for $mtx {
+            type Output = $mtx;
+
+            #[doc(alias = "Mtx_Subtract")]
+            fn sub(self, rhs: $mtx) -> Self::Output {
+                let mut out = self;
+                unsafe {
+                    citro3d_sys::Mtx_Subtract(out.as_raw_mut(), self.as_raw(), rhs.as_raw());
+                }
+                out
+            }
+        }
+
+        impl Mul<$mtx> for $mtx {
+            type Output = $mtx;
+
+            #[doc(alias = "Mtx_Multiply")]
+            fn mul(self, rhs: $mtx) -> Self::Output {
+                let mut out = self;
+                unsafe {
+                    citro3d_sys::Mtx_Multiply(out.as_raw_mut(), self.as_raw(), rhs.as_raw());
+                }
+                out
+            }
+        }
+
+        impl Add<&$mtx> for &$mtx {
+            type Output = $mtx;
+
+            fn add(self, rhs: &$mtx) -> Self::Output {
+                *self + *rhs
+            }
+        }
+
+        impl Sub<&$mtx> for &$mtx {
+            type Output = $mtx;
+
+            fn sub(self, rhs: &$mtx) -> Self::Output {
+                *self - *rhs
+            }
+        }
+
+        impl Mul<&$mtx> for &$mtx {
+            type Output = $mtx;
+
+            fn mul(self, rhs: &$mtx) -> Self::Output {
+                *self * *rhs
+            }
+        }
+
+        #[cfg(feature = "approx")]
+        #[doc(cfg(feature = "approx"))]
+        impl AbsDiffEq for $mtx {
+            type Epsilon = f32;
+
+            fn default_epsilon() -> Self::Epsilon {
+                f32::EPSILON.sqrt()
+            }
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                self.rows_wzyx()
+                    .into_iter()
+                    .zip(other.rows_wzyx().into_iter())
+                    .all(|(l, r)| l.abs_diff_eq(&r, epsilon))
+            }
+        }
+    )*};
+}
+
+impl_padded_matrix_ops!(Matrix2, Matrix3);
+
 // endregion
 
 #[cfg(feature = "approx")]
@@ -216,6 +384,7 @@ mod tests {
     use approx::assert_abs_diff_eq;
 
     use super::*;
+    use crate::math::Angle;
 
     #[test]
     fn fvec3() {
@@ -226,7 +395,18 @@ mod tests {
         assert_abs_diff_eq!(l - r, FVec3::splat(-1.0));
         assert_abs_diff_eq!(-l, FVec3::splat(-1.0));
         assert_abs_diff_eq!(l * 1.5, FVec3::splat(1.5));
+        assert_abs_diff_eq!(1.5 * l, FVec3::splat(1.5));
         assert_abs_diff_eq!(l / 2.0, FVec3::splat(0.5));
+
+        let mut v = l;
+        v += r;
+        assert_abs_diff_eq!(v, FVec3::splat(3.0));
+        v -= l;
+        assert_abs_diff_eq!(v, r);
+        v *= 2.0;
+        assert_abs_diff_eq!(v, FVec3::splat(4.0));
+        v /= 4.0;
+        assert_abs_diff_eq!(v, l);
     }
 
     #[test]
@@ -238,7 +418,18 @@ mod tests {
         assert_abs_diff_eq!(l - r, FVec4::splat(-1.0));
         assert_abs_diff_eq!(-l, FVec4::splat(-1.0));
         assert_abs_diff_eq!(l * 1.5, FVec4::splat(1.5));
+        assert_abs_diff_eq!(1.5 * l, FVec4::splat(1.5));
         assert_abs_diff_eq!(l / 2.0, FVec4::splat(0.5));
+
+        let mut v = l;
+        v += r;
+        assert_abs_diff_eq!(v, FVec4::splat(3.0));
+        v -= l;
+        assert_abs_diff_eq!(v, r);
+        v *= 2.0;
+        assert_abs_diff_eq!(v, FVec4::splat(4.0));
+        v /= 4.0;
+        assert_abs_diff_eq!(v, l);
     }
 
     #[test]
@@ -252,6 +443,51 @@ mod tests {
         assert_abs_diff_eq!(&(l - r), &Matrix3::diagonal(0.0, 1.0, 2.0));
     }
 
+    #[test]
+    fn matrix3_mul_fvec3() {
+        let m = Matrix3::diagonal(1.0, 2.0, 3.0);
+        assert_abs_diff_eq!(&m * FVec3::splat(1.0), FVec3::new(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn matrix2() {
+        let l = Matrix2::from_rows([[1.0, 2.0], [3.0, 4.0]]);
+        let r = Matrix2::identity();
+        let (l, r) = (&l, &r);
+
+        assert_abs_diff_eq!(&(l * r), l);
+        assert_abs_diff_eq!(&(r * l), l);
+        assert_abs_diff_eq!(&(l + r), &Matrix2::from_rows([[2.0, 2.0], [3.0, 5.0]]));
+        assert_abs_diff_eq!(&(l - r), &Matrix2::from_rows([[0.0, 2.0], [3.0, 3.0]]));
+        assert_eq!(l.transpose().rows_xy(), [[1.0, 3.0], [2.0, 4.0]]);
+    }
+
+    #[test]
+    fn matrix4_mul_order() {
+        let mut translate = Matrix4::identity();
+        translate.translate(1.0, 0.0, 0.0);
+        let mut rotate = Matrix4::identity();
+        rotate.rotate_z(Angle::from_turns(0.5));
+
+        // Not the origin, since it isn't affected by the rotation
+        let point = FVec4::new(0.0, 1.0, 0.0, 1.0);
+
+        // `rotate * translate` translates first, then rotates
+        let translate_then_rotate = &rotate * &translate;
+        assert_abs_diff_eq!(
+            &translate_then_rotate * point,
+            FVec4::new(-1.0, -1.0, 0.0, 1.0),
+            epsilon = 1e-6
+        );
+
+        let rotate_then_translate = &translate * &rotate;
+        assert_abs_diff_eq!(
+            &rotate_then_translate * point,
+            FVec4::new(1.0, -1.0, 0.0, 1.0),
+            epsilon = 1e-6
+        );
+    }
+
     #[test]
     fn matrix4() {
         let l = Matrix4::diagonal(1.0, 2.0, 3.0, 4.0);
@@ -262,4 +498,25 @@ mod tests {
         assert_abs_diff_eq!(&(l + r), &Matrix4::diagonal(2.0, 3.0, 4.0, 5.0));
         assert_abs_diff_eq!(&(l - r), &Matrix4::diagonal(0.0, 1.0, 2.0, 3.0));
     }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn glam_conversions() {
+        let quat = glam::Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+        assert_abs_diff_eq!(
+            &Matrix4::from(quat),
+            &Matrix4::rotation_z(Angle::from_degrees(90.0))
+        );
+
+        let mat = glam::Mat3::from_cols_array(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+        let converted = Matrix3::from(mat);
+        assert_eq!(converted.rows_xyz()[0], [1.0, 4.0, 7.0]);
+        assert_eq!(glam::Mat3::from(converted), mat);
+
+        let mat = glam::Mat4::from_cols_array(&std::array::from_fn(|i| i as f32));
+        let converted = Matrix4::from(mat);
+        assert_eq!(converted[(0, 1)], mat.col(1).x);
+        assert_eq!(converted.rows_xyzw()[3], [3.0, 7.0, 11.0, 15.0]);
+        assert_eq!(glam::Mat4::from(&converted), mat);
+    }
 }
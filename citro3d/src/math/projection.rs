@@ -1,7 +1,7 @@
 use std::mem::MaybeUninit;
 use std::ops::Range;
 
-use super::Matrix4;
+use super::{Angle, Matrix4};
 
 /// Configuration for a 3D [projection](https://en.wikipedia.org/wiki/3D_projection).
 /// See specific `Kind` implementations for constructors, e.g.
@@ -31,14 +31,18 @@ impl<Kind> Projection<Kind> {
     ///
     /// ```
     /// # let _runner = test_runner::GdbRunner::default();
-    /// # use citro3d::math::{Projection, AspectRatio, CoordinateOrientation, Matrix4, ClipPlanes};
+    /// # use citro3d::math::{Angle, Projection, AspectRatio, CoordinateOrientation, Matrix4, ClipPlanes};
     /// let clip_planes = ClipPlanes {
     ///     near: 0.1,
     ///     far: 100.0,
     /// };
-    /// let mtx: Matrix4 = Projection::perspective(40.0,  AspectRatio::TopScreen, clip_planes)
-    ///     .coordinates(CoordinateOrientation::LeftHanded)
-    ///     .into();
+    /// let mtx: Matrix4 = Projection::perspective(
+    ///     Angle::from_degrees(40.0),
+    ///     AspectRatio::TopScreen,
+    ///     clip_planes,
+    /// )
+    /// .coordinates(CoordinateOrientation::LeftHanded)
+    /// .into();
     /// ```
     pub fn coordinates(mut self, orientation: CoordinateOrientation) -> Self {
         self.coordinates = orientation;
@@ -52,14 +56,18 @@ impl<Kind> Projection<Kind> {
     ///
     /// ```
     /// # let _runner = test_runner::GdbRunner::default();
-    /// # use citro3d::math::{Projection, AspectRatio, ScreenOrientation, Matrix4, ClipPlanes};
+    /// # use citro3d::math::{Angle, Projection, AspectRatio, ScreenOrientation, Matrix4, ClipPlanes};
     /// let clip_planes = ClipPlanes {
     ///     near: 0.1,
     ///     far: 100.0,
     /// };
-    /// let mtx: Matrix4 = Projection::perspective(40.0, AspectRatio::TopScreen, clip_planes)
-    ///     .screen(ScreenOrientation::None)
-    ///     .into();
+    /// let mtx: Matrix4 = Projection::perspective(
+    ///     Angle::from_degrees(40.0),
+    ///     AspectRatio::TopScreen,
+    ///     clip_planes,
+    /// )
+    /// .screen(ScreenOrientation::None)
+    /// .into();
     /// ```
     pub fn screen(mut self, orientation: ScreenOrientation) -> Self {
         self.rotation = orientation;
@@ -70,7 +78,7 @@ impl<Kind> Projection<Kind> {
 /// See [`Projection::perspective`].
 #[derive(Clone, Debug)]
 pub struct Perspective {
-    vertical_fov_radians: f32,
+    vertical_fov: Angle,
     aspect_ratio: AspectRatio,
     clip_planes: ClipPlanes,
     stereo: Option<StereoDisplacement>,
@@ -82,7 +90,7 @@ impl Projection<Perspective> {
     ///
     /// # Parameters
     ///
-    /// * `vertical_fov`: the vertical field of view, measured in radians
+    /// * `vertical_fov`: the vertical field of view
     /// * `aspect_ratio`: the aspect ratio of the projection
     /// * `clip_planes`: the near and far clip planes of the view frustum.
     ///   [`ClipPlanes`] are always defined by near and far values, regardless
@@ -101,21 +109,29 @@ impl Projection<Perspective> {
     ///     far: 100.0,
     /// };
     ///
-    /// let bottom: Matrix4 =
-    ///     Projection::perspective(PI / 4.0, AspectRatio::BottomScreen, clip_planes).into();
+    /// let bottom: Matrix4 = Projection::perspective(
+    ///     Angle::from_radians(PI / 4.0),
+    ///     AspectRatio::BottomScreen,
+    ///     clip_planes,
+    /// )
+    /// .into();
     ///
-    /// let top: Matrix4 =
-    ///     Projection::perspective(PI / 4.0, AspectRatio::TopScreen, clip_planes).into();
+    /// let top: Matrix4 = Projection::perspective(
+    ///     Angle::from_radians(PI / 4.0),
+    ///     AspectRatio::TopScreen,
+    ///     clip_planes,
+    /// )
+    /// .into();
     /// ```
     #[doc(alias = "Mtx_Persp")]
     #[doc(alias = "Mtx_PerspTilt")]
     pub fn perspective(
-        vertical_fov_radians: f32,
+        vertical_fov: Angle,
         aspect_ratio: AspectRatio,
         clip_planes: ClipPlanes,
     ) -> Self {
         Self::new(Perspective {
-            vertical_fov_radians,
+            vertical_fov,
             aspect_ratio,
             clip_planes,
             stereo: None,
@@ -134,7 +150,7 @@ impl Projection<Perspective> {
     /// #
     /// let (left, right) = StereoDisplacement::new(0.5, 2.0);
     /// let (left_eye, right_eye) = Projection::perspective(
-    ///     PI / 4.0,
+    ///     Angle::from_radians(PI / 4.0),
     ///     AspectRatio::TopScreen,
     ///     ClipPlanes {
     ///         near: 0.01,
@@ -167,7 +183,7 @@ impl Projection<Perspective> {
 impl From<Projection<Perspective>> for Matrix4 {
     fn from(projection: Projection<Perspective>) -> Self {
         let Perspective {
-            vertical_fov_radians,
+            vertical_fov,
             aspect_ratio,
             clip_planes,
             stereo,
@@ -183,7 +199,7 @@ impl From<Projection<Perspective>> for Matrix4 {
             unsafe {
                 make_mtx(
                     result.as_mut_ptr(),
-                    vertical_fov_radians,
+                    vertical_fov.radians(),
                     aspect_ratio.into(),
                     clip_planes.near,
                     clip_planes.far,
@@ -200,7 +216,7 @@ impl From<Projection<Perspective>> for Matrix4 {
             unsafe {
                 make_mtx(
                     result.as_mut_ptr(),
-                    vertical_fov_radians,
+                    vertical_fov.radians(),
                     aspect_ratio.into(),
                     clip_planes.near,
                     clip_planes.far,
@@ -373,6 +389,27 @@ impl StereoDisplacement {
 
         (left_eye, right_eye)
     }
+
+    /// Construct displacement for the left and right eyes, scaling the given
+    /// `max_interocular_distance` by the current position of the 3D slider.
+    /// When the slider is all the way down, both eyes will have no displacement.
+    ///
+    /// See [`StereoDisplacement::new`] for more details.
+    #[doc(alias = "osGet3DSliderState")]
+    pub fn from_3d_slider(max_interocular_distance: f32, screen_depth: f32) -> (Self, Self) {
+        let slider = ctru::os::current_3d_slider_state();
+        Self::new(max_interocular_distance * slider, screen_depth)
+    }
+}
+
+/// A pair of projection matrices, one for each eye, used for stereoscopic
+/// rendering on the top screen. See [`Matrix4::perspective_stereo`].
+#[derive(Clone, Copy, Debug)]
+pub struct StereoProjection {
+    /// The projection for the left eye.
+    pub left: Matrix4,
+    /// The projection for the right eye.
+    pub right: Matrix4,
 }
 
 /// Configuration for the clipping planes of a projection.
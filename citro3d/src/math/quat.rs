@@ -0,0 +1,293 @@
+//! Quaternions, for representing rotations.
+
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::ops::{Mul, MulAssign, Neg};
+
+use super::{Angle, FVec3, FVec4, Matrix4};
+
+/// A quaternion, representing a rotation in 3D space.
+///
+/// Internally, this is represented the same way as [`FVec4`], with the
+/// imaginary `ijk` components stored as `xyz` and the real component as `w`.
+///
+/// Quaternions compose with `*`, where `a * b` applies the rotation `b` first,
+/// the same as for matrices.
+///
+/// # Example
+///
+/// ```
+/// # let _runner = test_runner::GdbRunner::default();
+/// # use citro3d::math::{Angle, FQuat, FVec3};
+/// # use approx::assert_abs_diff_eq;
+/// let quarter_turn = FQuat::from_axis_angle(FVec3::new(0.0, 0.0, 1.0), Angle::from_degrees(90.0));
+/// let rotated = quarter_turn.rotate(FVec3::new(1.0, 0.0, 0.0));
+/// assert_abs_diff_eq!(rotated, FVec3::new(0.0, 1.0, 0.0), epsilon = 1e-6);
+/// ```
+#[doc(alias = "C3D_FQuat")]
+#[doc(alias = "Quat")]
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct FQuat(pub(crate) citro3d_sys::C3D_FQuat);
+
+/// Shorthand for [`FQuat`].
+pub type Quat = FQuat;
+
+impl FQuat {
+    /// Create a new quaternion from its components, where `w` is the real part.
+    #[doc(alias = "Quat_New")]
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        // `Quat_New` is a macro for `FVec4_New`
+        Self(unsafe { citro3d_sys::FVec4_New(x, y, z, w) })
+    }
+
+    /// The identity quaternion, which represents no rotation.
+    #[doc(alias = "Quat_Identity")]
+    pub fn identity() -> Self {
+        Self(unsafe { citro3d_sys::Quat_Identity() })
+    }
+
+    /// A rotation of `angle` around `axis`, counter-clockwise when looking down
+    /// the axis towards the origin. `axis` does not need to be normalized.
+    #[doc(alias = "Quat_FromAxisAngle")]
+    pub fn from_axis_angle(axis: FVec3, angle: Angle) -> Self {
+        Self(unsafe { citro3d_sys::Quat_FromAxisAngle(axis.normalize().0, angle.radians()) })
+    }
+
+    /// The rotation applied by a rotation matrix.
+    #[doc(alias = "Quat_FromMtx")]
+    pub fn from_matrix(matrix: &Matrix4) -> Self {
+        Self(unsafe { citro3d_sys::Quat_FromMtx(matrix.as_raw()) })
+    }
+
+    /// The quaternion's `x` (`i`) component.
+    pub fn x(self) -> f32 {
+        self.to_vec().x()
+    }
+
+    /// The quaternion's `y` (`j`) component.
+    pub fn y(self) -> f32 {
+        self.to_vec().y()
+    }
+
+    /// The quaternion's `z` (`k`) component.
+    pub fn z(self) -> f32 {
+        self.to_vec().z()
+    }
+
+    /// The quaternion's `w` (`r`) component, i.e. its real part.
+    pub fn w(self) -> f32 {
+        self.to_vec().w()
+    }
+
+    /// The quaternion's components as a vector.
+    pub fn to_vec(self) -> FVec4 {
+        FVec4(self.0)
+    }
+
+    /// The dot product of two quaternions.
+    #[doc(alias = "Quat_Dot")]
+    pub fn dot(self, rhs: Self) -> f32 {
+        unsafe { citro3d_sys::FVec4_Dot(self.0, rhs.0) }
+    }
+
+    /// Normalize the quaternion to a magnitude of `1.0`. Only normalized
+    /// quaternions represent rotations.
+    #[doc(alias = "Quat_Normalize")]
+    #[must_use]
+    pub fn normalize(self) -> Self {
+        Self(unsafe { citro3d_sys::FVec4_Normalize(self.0) })
+    }
+
+    /// The conjugate of the quaternion. For a normalized quaternion, this is
+    /// the opposite rotation.
+    #[doc(alias = "Quat_Conjugate")]
+    #[must_use]
+    pub fn conjugate(self) -> Self {
+        Self(unsafe { citro3d_sys::Quat_Conjugate(self.0) })
+    }
+
+    /// The inverse of the quaternion.
+    #[doc(alias = "Quat_Inverse")]
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        Self(unsafe { citro3d_sys::Quat_Inverse(self.0) })
+    }
+
+    /// Rotate a vector by this quaternion.
+    #[doc(alias = "Quat_CrossFVec3")]
+    pub fn rotate(self, v: FVec3) -> FVec3 {
+        FVec3(unsafe { citro3d_sys::Quat_CrossFVec3(self.0, v.0) })
+    }
+
+    /// Spherically interpolate between two rotations, by `t` from `0.0`
+    /// (`self`) to `1.0` (`end`), taking the shortest path between them.
+    /// This rotates at a constant speed, e.g. for smooth camera movement.
+    ///
+    /// Both quaternions should be normalized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::{Angle, FQuat, FVec3};
+    /// # use approx::assert_abs_diff_eq;
+    /// let up = FVec3::new(0.0, 1.0, 0.0);
+    /// let start = FQuat::identity();
+    /// let end = FQuat::from_axis_angle(up, Angle::from_degrees(90.0));
+    ///
+    /// let halfway = start.slerp(end, 0.5);
+    /// assert_abs_diff_eq!(
+    ///     halfway,
+    ///     FQuat::from_axis_angle(up, Angle::from_degrees(45.0)),
+    ///     epsilon = 1e-6
+    /// );
+    /// ```
+    #[must_use]
+    pub fn slerp(self, end: Self, t: f32) -> Self {
+        let mut end = end.to_vec();
+        let mut cos = self.dot(Self(end.0));
+        // q and -q are the same rotation, so go the short way around
+        if cos < 0.0 {
+            end = -end;
+            cos = -cos;
+        }
+
+        let start = self.to_vec();
+        let blended = if cos > 0.9995 {
+            // too close to divide by sin(theta), but linear is accurate enough here
+            start + (end - start) * t
+        } else {
+            let theta = cos.acos();
+            let sin = theta.sin();
+            start * (((1.0 - t) * theta).sin() / sin) + end * ((t * theta).sin() / sin)
+        };
+
+        Self(blended.0).normalize()
+    }
+
+    /// The rotation matrix equivalent to this quaternion.
+    #[doc(alias = "Mtx_FromQuat")]
+    pub fn to_matrix(self) -> Matrix4 {
+        let mut out = MaybeUninit::uninit();
+        unsafe {
+            citro3d_sys::Mtx_FromQuat(out.as_mut_ptr(), self.0);
+            Matrix4::from_raw(out.assume_init())
+        }
+    }
+}
+
+impl Default for FQuat {
+    /// The identity quaternion.
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Mul for FQuat {
+    type Output = Self;
+
+    #[doc(alias = "Quat_Multiply")]
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(unsafe { citro3d_sys::Quat_Multiply(self.0, rhs.0) })
+    }
+}
+
+impl MulAssign for FQuat {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Neg for FQuat {
+    type Output = Self;
+
+    #[doc(alias = "Quat_Negate")]
+    fn neg(self) -> Self::Output {
+        Self(unsafe { citro3d_sys::FVec4_Negate(self.0) })
+    }
+}
+
+impl PartialEq for FQuat {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_vec() == other.to_vec()
+    }
+}
+
+impl From<FQuat> for Matrix4 {
+    fn from(quat: FQuat) -> Self {
+        quat.to_matrix()
+    }
+}
+
+impl fmt::Debug for FQuat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FQuat")
+            .field("x", &self.x())
+            .field("y", &self.y())
+            .field("z", &self.z())
+            .field("w", &self.w())
+            .finish()
+    }
+}
+
+#[cfg(feature = "approx")]
+#[doc(cfg(feature = "approx"))]
+impl approx::AbsDiffEq for FQuat {
+    type Epsilon = f32;
+
+    fn default_epsilon() -> Self::Epsilon {
+        <FVec4 as approx::AbsDiffEq>::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        approx::AbsDiffEq::abs_diff_eq(&self.to_vec(), &other.to_vec(), epsilon)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::Quat> for FQuat {
+    fn from(quat: glam::Quat) -> Self {
+        Self::new(quat.x, quat.y, quat.z, quat.w)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<FQuat> for glam::Quat {
+    fn from(quat: FQuat) -> Self {
+        glam::Quat::from_xyzw(quat.x(), quat.y(), quat.z(), quat.w())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn matches_matrix_rotation() {
+        let angle = Angle::from_degrees(30.0);
+        let quat = FQuat::from_axis_angle(FVec3::new(1.0, 0.0, 0.0), angle);
+        assert_abs_diff_eq!(quat.to_matrix(), Matrix4::rotation_x(angle));
+        assert_abs_diff_eq!(FQuat::from_matrix(&quat.to_matrix()), quat);
+    }
+
+    #[test]
+    fn multiply_and_slerp() {
+        let axis = FVec3::new(0.0, 1.0, 0.0);
+        let a = FQuat::from_axis_angle(axis, Angle::from_degrees(20.0));
+        let b = FQuat::from_axis_angle(axis, Angle::from_degrees(70.0));
+
+        assert_abs_diff_eq!(
+            a * b,
+            FQuat::from_axis_angle(axis, Angle::from_degrees(90.0))
+        );
+        assert_abs_diff_eq!(a * a.conjugate(), FQuat::identity());
+
+        assert_abs_diff_eq!(a.slerp(b, 0.0), a);
+        assert_abs_diff_eq!(a.slerp(b, 1.0), b);
+        // -b is the same rotation, so the interpolation shouldn't change
+        assert_abs_diff_eq!(a.slerp(-b, 0.5), a.slerp(b, 0.5));
+    }
+}
@@ -0,0 +1,354 @@
+//! Procedural textures, generated by the GPU from a few parameters and lookup
+//! tables instead of being sampled from texture memory.
+//!
+//! A procedural texture is made up of:
+//!
+//! * a [`ProcTex`], configuring how the `(u, v)` coordinates are clamped,
+//!   perturbed with noise, and combined into a single value,
+//! * mapping [`Lut`]s, for the noise and for mapping the combined value, and
+//! * a [`ColorLut`], mapping the final value to a color.
+//!
+//! All of these are bound with the corresponding methods on [`Instance`](crate::Instance),
+//! after which the procedural texture can be used in a [`TexEnv`](crate::texenv::TexEnv)
+//! stage as [`Source::Texture3`](crate::texenv::Source::Texture3).
+//!
+//! # Example
+//!
+//! ```
+//! # let _runner = test_runner::GdbRunner::default();
+//! # use citro3d::proctex::{Clamp, ColorLut, Coordinates, Lut, LutId, MapFunc, ProcTex};
+//! # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+//! # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+//! let colors: Vec<u32> = (0..=255).map(|i| 0xFF000000 | (i << 16) | (i << 8) | i).collect();
+//!
+//! let mut proctex = ProcTex::new(0, colors.len());
+//! proctex.set_clamp(Clamp::MirroredRepeat, Clamp::MirroredRepeat);
+//! proctex.set_combiner(false, MapFunc::Sqrt2, MapFunc::Sqrt2);
+//! proctex.set_noise(Coordinates::UV, 0.2, 0.3, 0.0);
+//! proctex.set_noise_enabled(true);
+//!
+//! instance.bind_proctex(0, Some(proctex));
+//! instance.bind_proctex_lut(LutId::Noise, Some(Lut::from_fn(|x| x)));
+//! instance.bind_proctex_lut(LutId::RgbMap, Some(Lut::from_fn(|x| x)));
+//! instance.bind_proctex_lut(LutId::AlphaMap, Some(Lut::from_fn(|x| x)));
+//! instance.bind_proctex_color_lut(Some(ColorLut::from_colors(&colors).unwrap()));
+//! ```
+
+use std::mem::MaybeUninit;
+
+/// Number of entries in a [`Lut`].
+const LUT_LEN: usize = 128;
+/// Maximum number of entries in a [`ColorLut`].
+const COLOR_LUT_LEN: usize = 256;
+
+/// Procedural texture parameters.
+#[doc(alias = "C3D_ProcTex")]
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct ProcTex(citro3d_sys::C3D_ProcTex);
+
+impl ProcTex {
+    /// Create procedural texture parameters with default settings, using `len`
+    /// entries of the bound [`ColorLut`] starting at `offset`.
+    ///
+    /// # Panics
+    ///
+    /// If `offset + len` is greater than the size of a [`ColorLut`] (256).
+    #[doc(alias = "C3D_ProcTexInit")]
+    pub fn new(offset: usize, len: usize) -> Self {
+        assert!(
+            offset + len <= COLOR_LUT_LEN,
+            "procedural texture color range must fit in the color LUT"
+        );
+
+        let raw = unsafe {
+            let mut raw = MaybeUninit::zeroed();
+            citro3d_sys::C3D_ProcTexInit(raw.as_mut_ptr(), offset as _, len as _);
+            raw.assume_init()
+        };
+        Self(raw)
+    }
+
+    /// Configure the noise applied to the given `coordinates`. Noise must also be
+    /// enabled with [`Self::set_noise_enabled`] to take effect.
+    #[doc(alias = "C3D_ProcTexNoiseCoefs")]
+    pub fn set_noise(
+        &mut self,
+        coordinates: Coordinates,
+        amplitude: f32,
+        frequency: f32,
+        phase: f32,
+    ) {
+        unsafe {
+            citro3d_sys::C3D_ProcTexNoiseCoefs(
+                &mut self.0,
+                coordinates as _,
+                amplitude,
+                frequency,
+                phase,
+            );
+        }
+    }
+
+    /// Enable or disable noise. This uses the [`LutId::Noise`] LUT.
+    #[doc(alias = "C3D_ProcTexNoiseEnable")]
+    pub fn set_noise_enabled(&mut self, enabled: bool) {
+        unsafe {
+            citro3d_sys::C3D_ProcTexNoiseEnable(&mut self.0, enabled);
+        }
+    }
+
+    /// Set how the `u` and `v` coordinates are clamped.
+    #[doc(alias = "C3D_ProcTexClamp")]
+    pub fn set_clamp(&mut self, u: Clamp, v: Clamp) {
+        unsafe {
+            citro3d_sys::C3D_ProcTexClamp(
+                &mut self.0,
+                u as ctru_sys::GPU_PROCTEX_CLAMP,
+                v as ctru_sys::GPU_PROCTEX_CLAMP,
+            );
+        }
+    }
+
+    /// Set how the `u` and `v` coordinates are combined into a single value,
+    /// which is then looked up in the [`LutId::RgbMap`] (and [`LutId::AlphaMap`],
+    /// if `separate_alpha` is `true`) LUT.
+    #[doc(alias = "C3D_ProcTexCombiner")]
+    pub fn set_combiner(&mut self, separate_alpha: bool, rgb: MapFunc, alpha: MapFunc) {
+        unsafe {
+            citro3d_sys::C3D_ProcTexCombiner(
+                &mut self.0,
+                separate_alpha,
+                rgb as ctru_sys::GPU_PROCTEX_MAPFUNC,
+                alpha as ctru_sys::GPU_PROCTEX_MAPFUNC,
+            );
+        }
+    }
+
+    /// Set how the `u` and `v` coordinates are shifted, e.g. to make a brick pattern.
+    #[doc(alias = "C3D_ProcTexShift")]
+    pub fn set_shift(&mut self, u: Shift, v: Shift) {
+        unsafe {
+            citro3d_sys::C3D_ProcTexShift(
+                &mut self.0,
+                u as ctru_sys::GPU_PROCTEX_SHIFT,
+                v as ctru_sys::GPU_PROCTEX_SHIFT,
+            );
+        }
+    }
+
+    /// Set the filter used when looking up the color LUT.
+    #[doc(alias = "C3D_ProcTexFilter")]
+    pub fn set_filter(&mut self, filter: Filter) {
+        unsafe {
+            citro3d_sys::C3D_ProcTexFilter(&mut self.0, filter as ctru_sys::GPU_PROCTEX_FILTER);
+        }
+    }
+
+    /// Set the level-of-detail bias used when looking up the color LUT.
+    #[doc(alias = "C3D_ProcTexLodBias")]
+    pub fn set_lod_bias(&mut self, bias: f32) {
+        unsafe {
+            citro3d_sys::C3D_ProcTexLodBias(&mut self.0, bias);
+        }
+    }
+
+    pub(crate) fn as_raw_mut(&mut self) -> &mut citro3d_sys::C3D_ProcTex {
+        &mut self.0
+    }
+}
+
+impl std::fmt::Debug for ProcTex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProcTex").finish_non_exhaustive()
+    }
+}
+
+/// A lookup table used to map values in a procedural texture.
+#[doc(alias = "C3D_ProcTexLut")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Lut(citro3d_sys::C3D_ProcTexLut);
+
+impl Lut {
+    /// Create a LUT by memoizing a function over the range `[0, 1]`.
+    #[doc(alias = "ProcTexLut_FromArray")]
+    pub fn from_fn(mut f: impl FnMut(f32) -> f32) -> Self {
+        let mut data = [0.0f32; LUT_LEN + 1];
+        for (i, v) in data.iter_mut().enumerate() {
+            *v = f(i as f32 / LUT_LEN as f32);
+        }
+
+        let lut = unsafe {
+            let mut lut = MaybeUninit::zeroed();
+            citro3d_sys::ProcTexLut_FromArray(lut.as_mut_ptr(), data.as_ptr());
+            lut.assume_init()
+        };
+        Self(lut)
+    }
+
+    pub(crate) fn as_raw_mut(&mut self) -> &mut citro3d_sys::C3D_ProcTexLut {
+        &mut self.0
+    }
+}
+
+/// A lookup table mapping the final value of a procedural texture to a color.
+#[doc(alias = "C3D_ProcTexColorLut")]
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct ColorLut(citro3d_sys::C3D_ProcTexColorLut);
+
+impl ColorLut {
+    /// Create a color LUT from the given colors, in `0xAABBGGRR` format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidSize`](crate::Error::InvalidSize) if more than
+    /// 256 colors are given.
+    #[doc(alias = "ProcTexColorLut_Write")]
+    pub fn from_colors(colors: &[u32]) -> crate::Result<Self> {
+        if colors.len() > COLOR_LUT_LEN {
+            return Err(crate::Error::InvalidSize);
+        }
+
+        let lut = unsafe {
+            let mut lut = MaybeUninit::zeroed();
+            citro3d_sys::ProcTexColorLut_Write(
+                lut.as_mut_ptr(),
+                colors.as_ptr(),
+                0,
+                colors.len() as _,
+            );
+            lut.assume_init()
+        };
+        Ok(Self(lut))
+    }
+
+    pub(crate) fn as_raw_mut(&mut self) -> &mut citro3d_sys::C3D_ProcTexColorLut {
+        &mut self.0
+    }
+}
+
+impl std::fmt::Debug for ColorLut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ColorLut").finish_non_exhaustive()
+    }
+}
+
+/// The procedural texture state bound to an [`Instance`](crate::Instance).
+/// citro3d keeps pointers to all of these, so they are kept alive here.
+#[derive(Debug, Default)]
+pub(crate) struct Bindings {
+    pub(crate) proctex: Option<Box<ProcTex>>,
+    pub(crate) luts: [Option<Box<Lut>>; 3],
+    pub(crate) color_lut: Option<Box<ColorLut>>,
+}
+
+/// Which coordinates noise is applied to.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Coordinates {
+    /// Only the `u` coordinate.
+    #[doc(alias = "C3D_ProcTex_U")]
+    U = citro3d_sys::C3D_ProcTex_U,
+    /// Only the `v` coordinate.
+    #[doc(alias = "C3D_ProcTex_V")]
+    V = citro3d_sys::C3D_ProcTex_V,
+    /// Both coordinates.
+    #[doc(alias = "C3D_ProcTex_UV")]
+    UV = citro3d_sys::C3D_ProcTex_UV,
+}
+
+/// How procedural texture coordinates are clamped.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[doc(alias = "GPU_PROCTEX_CLAMP")]
+pub enum Clamp {
+    /// Clamp to zero.
+    ToZero = ctru_sys::GPU_PT_CLAMP_TO_ZERO,
+    /// Clamp to the edge.
+    ToEdge = ctru_sys::GPU_PT_CLAMP_TO_EDGE,
+    /// Repeat the texture.
+    Repeat = ctru_sys::GPU_PT_REPEAT,
+    /// Repeat the texture, mirroring every other repetition.
+    MirroredRepeat = ctru_sys::GPU_PT_MIRRORED_REPEAT,
+    /// Like `ToEdge`, but the value is only ever either `0` or `1`.
+    Pulse = ctru_sys::GPU_PT_PULSE,
+}
+
+/// The function combining the `u` and `v` coordinates into a single value.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[doc(alias = "GPU_PROCTEX_MAPFUNC")]
+pub enum MapFunc {
+    /// `u`
+    U = ctru_sys::GPU_PT_U,
+    /// `u²`
+    U2 = ctru_sys::GPU_PT_U2,
+    /// `v`
+    V = ctru_sys::GPU_PT_V,
+    /// `v²`
+    V2 = ctru_sys::GPU_PT_V2,
+    /// `(u + v) / 2`
+    Add = ctru_sys::GPU_PT_ADD,
+    /// `(u² + v²) / 2`
+    Add2 = ctru_sys::GPU_PT_ADD2,
+    /// `sqrt(u² + v²)`
+    Sqrt2 = ctru_sys::GPU_PT_SQRT2,
+    /// `min(u, v)`
+    Min = ctru_sys::GPU_PT_MIN,
+    /// `max(u, v)`
+    Max = ctru_sys::GPU_PT_MAX,
+    /// `((u + v) / 2 + sqrt(u² + v²)) / 2`
+    RMax = ctru_sys::GPU_PT_RMAX,
+}
+
+/// How procedural texture coordinates are shifted.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[doc(alias = "GPU_PROCTEX_SHIFT")]
+pub enum Shift {
+    /// No shift.
+    None = ctru_sys::GPU_PT_NONE,
+    /// Shift odd rows/columns.
+    Odd = ctru_sys::GPU_PT_ODD,
+    /// Shift even rows/columns.
+    Even = ctru_sys::GPU_PT_EVEN,
+}
+
+/// The filter used when looking up a procedural texture's [`ColorLut`].
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[doc(alias = "GPU_PROCTEX_FILTER")]
+#[allow(missing_docs)]
+pub enum Filter {
+    Nearest = ctru_sys::GPU_PT_NEAREST,
+    Linear = ctru_sys::GPU_PT_LINEAR,
+    NearestMipNearest = ctru_sys::GPU_PT_NEAREST_MIP_NEAREST,
+    LinearMipNearest = ctru_sys::GPU_PT_LINEAR_MIP_NEAREST,
+    NearestMipLinear = ctru_sys::GPU_PT_NEAREST_MIP_LINEAR,
+    LinearMipLinear = ctru_sys::GPU_PT_LINEAR_MIP_LINEAR,
+}
+
+/// Which of the procedural texture's mapping [`Lut`]s to bind.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[doc(alias = "GPU_PROCTEX_LUTID")]
+pub enum LutId {
+    /// The LUT used to compute noise.
+    Noise = ctru_sys::GPU_LUT_NOISE,
+    /// The LUT used to map the combined RGB value.
+    RgbMap = ctru_sys::GPU_LUT_RGBMAP,
+    /// The LUT used to map the combined alpha value, if separate.
+    AlphaMap = ctru_sys::GPU_LUT_ALPHAMAP,
+}
+
+impl LutId {
+    pub(crate) fn index(self) -> usize {
+        match self {
+            Self::Noise => 0,
+            Self::RgbMap => 1,
+            Self::AlphaMap => 2,
+        }
+    }
+}
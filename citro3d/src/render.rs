@@ -1,27 +1,51 @@
 //! This module provides render target types and options for controlling transfer
 //! of data to the GPU, including the format of color and depth data to be rendered.
 
-use std::cell::RefMut;
+use std::cell::{Cell, RefMut};
 
 use citro3d_sys::{
-    C3D_RenderTarget, C3D_RenderTargetCreate, C3D_RenderTargetDelete, C3D_DEPTHTYPE,
+    C3D_RenderTarget, C3D_RenderTargetCreate, C3D_RenderTargetCreateFromTex,
+    C3D_RenderTargetDelete, C3D_DEPTHTYPE,
 };
-use ctru::services::gfx::Screen;
+use ctru::services::gfx::{RawFrameBuffer, Screen};
 use ctru::services::gspgpu::FramebufferFormat;
 use ctru_sys::{GPU_COLORBUF, GPU_DEPTHBUF};
 
+use crate::texture::{self, Tex, TexFormat};
 use crate::{Error, Result};
 
+mod effect;
 mod transfer;
 
+pub use effect::{
+    BlendEquation, BlendFactor, CullMode, DepthFunc, EarlyDepthFunc, LogicOp, StencilOp, TestFunc,
+    WriteMask,
+};
+pub use transfer::{TransferFlags, TransferFormat};
+
 /// A render target for `citro3d`. Frame data will be written to this target
 /// to be rendered on the GPU and displayed on the screen.
 #[doc(alias = "C3D_RenderTarget")]
 pub struct Target<'screen> {
     raw: *mut citro3d_sys::C3D_RenderTarget,
-    // This is unused after construction, but ensures unique access to the
-    // screen this target writes to during rendering
-    _screen: RefMut<'screen, dyn Screen>,
+    color_format: ColorFormat,
+    depth_format: Option<DepthFormat>,
+    anti_aliasing: AntiAliasing,
+    /// Clear applied by [`Instance::select_render_target`](crate::Instance::select_render_target)
+    clear_on_select: Option<(ClearFlags, u32, u32)>,
+    /// The frame in which `clear_on_select` was last applied
+    cleared_frame: Cell<Option<u64>>,
+    /// Ensures unique access to the screen or texture this target writes to
+    /// during rendering
+    output: Output<'screen>,
+}
+
+/// Whatever a [`Target`] is rendering to, which must outlive the target.
+// Textures are never read, they are just held to keep the output borrowed.
+#[allow(dead_code)]
+enum Output<'screen> {
+    Screen(RefMut<'screen, dyn Screen>),
+    Texture(&'screen mut Tex),
 }
 
 impl Drop for Target<'_> {
@@ -37,9 +61,16 @@ impl<'screen> Target<'screen> {
     /// Create a new render target with the specified size, color format,
     /// and depth format.
     ///
+    /// The color format is taken from the screen's framebuffer format. Pass
+    /// `None` as the `depth_format` to create a target without a depth buffer,
+    /// e.g. for color-only passes like blurring.
+    ///
+    /// The color and depth buffers are always allocated in VRAM, so they don't
+    /// compete with vertex buffers for linear memory.
+    ///
     /// # Errors
     ///
-    /// Fails if the target could not be created.
+    /// Returns [`Error::RenderTargetCreationFailed`] if the target could not be created.
     #[doc(alias = "C3D_RenderTargetCreate")]
     #[doc(alias = "C3D_RenderTargetSetOutput")]
     pub fn new(
@@ -47,8 +78,55 @@ impl<'screen> Target<'screen> {
         height: usize,
         screen: RefMut<'screen, dyn Screen>,
         depth_format: Option<DepthFormat>,
+    ) -> Result<Self> {
+        Self::new_antialiased(width, height, screen, depth_format, AntiAliasing::None)
+    }
+
+    /// Create a new render target like [`Self::new`], which is anti-aliased by
+    /// downscaling when it is transferred to the screen.
+    ///
+    /// `width` and `height` are the size of the screen's framebuffer; the target
+    /// itself is allocated at that size multiplied by the
+    /// [`AntiAliasing::scale`] factors, so it uses correspondingly more VRAM and
+    /// fill rate.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::RenderTargetCreationFailed`] if the target could not be created.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::{AntiAliasing, DepthFormat, Target};
+    /// # use std::cell::RefMut;
+    /// # use ctru::services::gfx::{RawFrameBuffer, Screen};
+    /// # fn create_target(mut screen: RefMut<'_, dyn Screen>) {
+    /// let RawFrameBuffer { width, height, .. } = screen.raw_framebuffer();
+    ///
+    /// let target = Target::new_antialiased(
+    ///     width,
+    ///     height,
+    ///     screen,
+    ///     Some(DepthFormat::Depth24Stencil8),
+    ///     AntiAliasing::Scale2x2,
+    /// )
+    /// .unwrap();
+    /// # }
+    /// ```
+    #[doc(alias = "C3D_RenderTargetCreate")]
+    #[doc(alias = "C3D_RenderTargetSetOutput")]
+    #[doc(alias = "GX_TRANSFER_SCALING")]
+    pub fn new_antialiased(
+        width: usize,
+        height: usize,
+        screen: RefMut<'screen, dyn Screen>,
+        depth_format: Option<DepthFormat>,
+        anti_aliasing: AntiAliasing,
     ) -> Result<Self> {
         let color_format: ColorFormat = screen.framebuffer_format().into();
+        let (scale_x, scale_y) = anti_aliasing.scale();
+        let (width, height) = (width * scale_x, height * scale_y);
 
         let raw = unsafe {
             C3D_RenderTargetCreate(
@@ -60,31 +138,209 @@ impl<'screen> Target<'screen> {
         };
 
         if raw.is_null() {
-            return Err(Error::FailedToInitialize);
+            return Err(Error::RenderTargetCreationFailed {
+                width,
+                height,
+                color_format,
+                depth_format,
+            });
         }
 
+        let target = Self {
+            raw,
+            color_format,
+            depth_format,
+            anti_aliasing,
+            clear_on_select: None,
+            cleared_frame: Cell::new(None),
+            output: Output::Screen(screen),
+        };
         // Set the render target to actually output to the given screen
-        let flags = transfer::Flags::default()
-            .in_format(color_format.into())
-            .out_format(color_format.into());
+        target.link_output();
+
+        Ok(target)
+    }
+
+    /// Create a new render target which draws to the given texture, so that
+    /// the rendered image can be sampled by later draw calls
+    /// (e.g. for post-processing).
+    ///
+    /// The texture stays mutably borrowed for as long as the target exists, so
+    /// it can't be bound for sampling or uploaded to while it is being rendered
+    /// into. Drop the target before using the texture.
+    ///
+    /// # Parameters
+    ///
+    /// * `texture`: the texture to render to. Its [`TexFormat`] must be one of the
+    ///   formats supported as a [`ColorFormat`]. Rendering is faster if it was
+    ///   allocated in [`MemoryRegion::Vram`](texture::MemoryRegion::Vram).
+    /// * `face`: the face of the texture to render to. Use [`texture::Face::TEX_2D`]
+    ///   for 2D textures.
+    /// * `level`: the mipmap level of the texture to render to.
+    /// * `depth_format`: the format of the depth buffer to allocate (in VRAM) for
+    ///   the target, if any.
+    ///
+    /// Note that textures are not displayed on the rotated 3DS screens, so the
+    /// projection used to draw to them should generally not be rotated
+    /// (see [`ScreenOrientation`](crate::math::ScreenOrientation)).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedRenderTargetFormat`] if the texture's format
+    /// cannot be rendered to, or [`Error::RenderTargetCreationFailed`] if the
+    /// target could not be created.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::Target;
+    /// # use citro3d::texture::{Face, Tex, TexParams};
+    /// let mut tex = Tex::new(TexParams::new_2d(128, 128).use_vram(true)).unwrap();
+    /// let target = Target::from_texture(&mut tex, Face::TEX_2D, 0, None).unwrap();
+    /// ```
+    #[doc(alias = "C3D_RenderTargetCreateFromTex")]
+    pub fn from_texture(
+        texture: &'screen mut Tex,
+        face: texture::Face,
+        level: u8,
+        depth_format: Option<DepthFormat>,
+    ) -> Result<Self> {
+        let color_format = ColorFormat::try_from(texture.format())?;
+
+        let raw = unsafe {
+            C3D_RenderTargetCreateFromTex(
+                texture.as_raw_mut(),
+                face as ctru_sys::GPU_TEXFACE,
+                level.into(),
+                depth_format.map_or(C3D_DEPTHTYPE { __i: -1 }, DepthFormat::as_raw),
+            )
+        };
+
+        if raw.is_null() {
+            return Err(Error::RenderTargetCreationFailed {
+                width: texture.width().into(),
+                height: texture.height().into(),
+                color_format,
+                depth_format,
+            });
+        }
+
+        Ok(Self {
+            raw,
+            color_format,
+            depth_format,
+            anti_aliasing: AntiAliasing::None,
+            clear_on_select: None,
+            cleared_frame: Cell::new(None),
+            output: Output::Texture(texture),
+        })
+    }
+
+    /// Change the screen this target is transferred to at the end of each
+    /// frame, e.g. to move a target between the left and right sides of the
+    /// top screen.
+    ///
+    /// Each screen (and each side of the top screen, in 3D mode) displays a
+    /// single target. For stereoscopic 3D, split the top screen with
+    /// [`TopScreen3D::split_mut`](ctru::services::gfx::TopScreen3D::split_mut)
+    /// and create one target for each side.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidRenderTarget`] if this target renders to a texture
+    ///   rather than a screen.
+    /// * [`Error::InvalidSize`] if the screen's framebuffer is not the size this
+    ///   target was created for.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::Target;
+    /// # use ctru::services::gfx::{Gfx, RawFrameBuffer, Screen, TopScreen3D};
+    /// # fn stereo(gfx: &Gfx) {
+    /// let top_screen = TopScreen3D::from(&gfx.top_screen);
+    /// let (mut left, right) = top_screen.split_mut();
+    ///
+    /// let RawFrameBuffer { width, height, .. } = left.raw_framebuffer();
+    /// let mut target = Target::new(width, height, left, None).unwrap();
+    ///
+    /// // Show the target on the right eye instead
+    /// target.set_output(right).unwrap();
+    /// # }
+    /// ```
+    #[doc(alias = "C3D_RenderTargetSetOutput")]
+    pub fn set_output(&mut self, mut screen: RefMut<'screen, dyn Screen>) -> Result<()> {
+        if !matches!(self.output, Output::Screen(_)) {
+            return Err(Error::InvalidRenderTarget);
+        }
+
+        let (scale_x, scale_y) = self.anti_aliasing.scale();
+        let RawFrameBuffer { width, height, .. } = screen.raw_framebuffer();
+        let (target_width, target_height) = self.dimensions();
+        if (width * scale_x, height * scale_y) != (target_width as usize, target_height as usize) {
+            return Err(Error::InvalidSize);
+        }
+
+        // citro3d would otherwise keep transferring this target to the old screen.
+        // Linking another target to that screen unlinks this one, in which case
+        // the slot must be left alone, the same as in `C3D_RenderTargetDelete`.
+        unsafe {
+            if (*self.raw).linked {
+                citro3d_sys::C3D_RenderTargetDetachOutput(self.raw);
+            }
+        }
+
+        self.output = Output::Screen(screen);
+        self.link_output();
+        Ok(())
+    }
+
+    /// Link the target to its output screen, if it has one, so it is
+    /// transferred to the screen at the end of each frame.
+    fn link_output(&self) {
+        let Output::Screen(screen) = &self.output else {
+            return;
+        };
+
+        let screen_format = ColorFormat::from(screen.framebuffer_format());
+        let flags = TransferFlags::default()
+            .in_format(self.color_format.into())
+            .out_format(screen_format.into())
+            .scaling(self.anti_aliasing);
 
         unsafe {
             citro3d_sys::C3D_RenderTargetSetOutput(
-                raw,
+                self.raw,
                 screen.as_raw(),
                 screen.side().into(),
                 flags.bits(),
             );
         }
-
-        Ok(Self {
-            raw,
-            _screen: screen,
-        })
     }
 
     /// Clear the render target with the given 32-bit RGBA color and depth buffer value.
-    /// Use `flags` to specify whether color and/or depth should be overwritten.
+    /// Use `flags` to specify whether color and/or depth should be overwritten;
+    /// any buffer not included in `flags` is left untouched.
+    ///
+    /// `rgba_color` is packed with red in the most significant byte, i.e. `0xRRGGBBAA`.
+    /// `depth` is the raw value written to the depth buffer; only the lowest
+    /// bits are used, according to the target's [`DepthFormat`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::{ClearFlags, Target};
+    /// # fn clear_target(target: &mut Target<'_>) {
+    /// // Clear to opaque sky blue
+    /// target.clear(ClearFlags::ALL, 0x68_B0_D8_FF, 0);
+    ///
+    /// // Clear only the depth buffer, keeping the existing color
+    /// target.clear(ClearFlags::DEPTH, 0, 0);
+    /// # }
+    /// ```
     #[doc(alias = "C3D_RenderTargetClear")]
     pub fn clear(&mut self, flags: ClearFlags, rgba_color: u32, depth: u32) {
         unsafe {
@@ -92,6 +348,148 @@ impl<'screen> Target<'screen> {
         }
     }
 
+    /// Automatically [clear](Self::clear) the render target with the given
+    /// values the first time it is selected with
+    /// [`Instance::select_render_target`](crate::Instance::select_render_target)
+    /// in each frame, so it doesn't need to be cleared manually.
+    ///
+    /// Targets are not cleared on selection by default. Use
+    /// [`Self::disable_clear`] to turn it back off, e.g. for a target which
+    /// accumulates its contents across frames.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::{ClearFlags, Target};
+    /// # fn draw(instance: &mut citro3d::Instance, target: &mut Target<'_>) {
+    /// target.set_clear(ClearFlags::ALL, 0x68_B0_D8_FF, 0);
+    ///
+    /// instance.render_frame_with(|instance| {
+    ///     // The target is cleared here, no need to call `target.clear()`
+    ///     instance.select_render_target(target).unwrap();
+    /// });
+    /// # }
+    /// ```
+    pub fn set_clear(&mut self, flags: ClearFlags, rgba_color: u32, depth: u32) {
+        self.clear_on_select = Some((flags, rgba_color, depth));
+        self.cleared_frame.set(None);
+    }
+
+    /// Stop automatically clearing the render target when it is selected.
+    /// See [`Self::set_clear`].
+    pub fn disable_clear(&mut self) {
+        self.clear_on_select = None;
+    }
+
+    /// Apply the clear configured with [`Self::set_clear`], if this is the first
+    /// time the target is selected in `frame`.
+    pub(crate) fn clear_on_select(&self, frame: u64) {
+        if let Some((flags, rgba_color, depth)) = self.clear_on_select {
+            if self.cleared_frame.replace(Some(frame)) != Some(frame) {
+                unsafe {
+                    citro3d_sys::C3D_RenderTargetClear(self.raw, flags.bits(), rgba_color, depth);
+                }
+            }
+        }
+    }
+
+    /// The width of the target's framebuffer, in pixels.
+    ///
+    /// The 3DS screens are mounted sideways, so for a target rendering to a
+    /// screen this is the screen's physical height (e.g. 240 for the top screen).
+    /// Anti-aliased targets are larger than their output; see [`AntiAliasing::scale`].
+    pub fn width(&self) -> u32 {
+        self.dimensions().0
+    }
+
+    /// The height of the target's framebuffer, in pixels. See [`Self::width`].
+    pub fn height(&self) -> u32 {
+        self.dimensions().1
+    }
+
+    /// The width and height of the target's framebuffer, in pixels. This is the
+    /// area covered by the default viewport set by
+    /// [`Instance::select_render_target`](crate::Instance::select_render_target).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::Target;
+    /// # fn aspect_ratio(target: &Target<'_>) -> f32 {
+    /// // Screens are rotated, so the aspect ratio is height / width
+    /// let (width, height) = target.dimensions();
+    /// height as f32 / width as f32
+    /// # }
+    /// ```
+    pub fn dimensions(&self) -> (u32, u32) {
+        let frame_buf = unsafe { &(*self.raw).frameBuf };
+        (frame_buf.width.into(), frame_buf.height.into())
+    }
+
+    /// Copy the target's color buffer to the screen's current framebuffer,
+    /// converting it as described by `flags`, and wait for the copy to finish.
+    ///
+    /// This allows presenting a target manually, e.g. at the end of a chain of
+    /// post-processing passes, instead of linking it to a screen when it is
+    /// created. Call it after the frame rendering to this target has ended
+    /// (i.e. outside of [`Instance::render_frame_with`](crate::Instance::render_frame_with)),
+    /// then swap the screen's buffers as usual to display the result.
+    ///
+    /// `flags` should set the [input format](TransferFlags::in_format) to this
+    /// target's color format and the [output format](TransferFlags::out_format)
+    /// to the screen's framebuffer format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidSize`] if this target is not the size of the
+    /// screen's framebuffer, multiplied by the [scaling](TransferFlags::scaling)
+    /// in `flags`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::render::{AntiAliasing, ColorFormat, Target, TransferFlags};
+    /// # use ctru::services::gfx::Screen;
+    /// # fn present(target: &Target<'_>, screen: &mut dyn Screen) {
+    /// let flags = TransferFlags::default()
+    ///     .in_format(ColorFormat::RGBA8.into())
+    ///     .out_format(ColorFormat::from(screen.framebuffer_format()).into())
+    ///     .scaling(AntiAliasing::Scale2x2);
+    ///
+    /// target.transfer_to_screen(screen, flags).unwrap();
+    /// # }
+    /// ```
+    #[doc(alias = "C3D_SyncDisplayTransfer")]
+    pub fn transfer_to_screen(&self, screen: &mut dyn Screen, flags: TransferFlags) -> Result<()> {
+        let (scale_x, scale_y) = flags.scale();
+        let RawFrameBuffer {
+            ptr, width, height, ..
+        } = screen.raw_framebuffer();
+        let (target_width, target_height) = self.dimensions();
+        if (width * scale_x, height * scale_y) != (target_width as usize, target_height as usize) {
+            return Err(Error::InvalidSize);
+        }
+
+        unsafe {
+            citro3d_sys::C3D_SyncDisplayTransfer(
+                (*self.raw).frameBuf.colorBuf.cast(),
+                citro3d_sys::GX_BUFFER_DIM(target_width, target_height),
+                ptr.cast(),
+                citro3d_sys::GX_BUFFER_DIM(width as u32, height as u32),
+                flags.bits(),
+            );
+        }
+        Ok(())
+    }
+
+    /// The format of the target's depth buffer, or `None` if it has none.
+    pub fn depth_format(&self) -> Option<DepthFormat> {
+        self.depth_format
+    }
+
     /// Return the underlying `citro3d` render target for this target.
     pub(crate) fn as_raw(&self) -> *mut C3D_RenderTarget {
         self.raw
@@ -141,9 +539,27 @@ impl From<FramebufferFormat> for ColorFormat {
     }
 }
 
+impl TryFrom<TexFormat> for ColorFormat {
+    type Error = Error;
+
+    fn try_from(format: TexFormat) -> Result<Self> {
+        match format {
+            TexFormat::Rgba8 => Ok(Self::RGBA8),
+            TexFormat::Rgb8 => Ok(Self::RGB8),
+            TexFormat::Rgba5551 => Ok(Self::RGBA5551),
+            TexFormat::Rgb565 => Ok(Self::RGB565),
+            TexFormat::Rgba4 => Ok(Self::RGBA4),
+            _ => Err(Error::UnsupportedRenderTargetFormat(format)),
+        }
+    }
+}
+
 /// The depth buffer format to use when rendering.
+///
+/// Smaller formats use less VRAM, while [`DepthFormat::Depth24Stencil8`] is
+/// required to use the stencil test.
 #[repr(u32)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[doc(alias = "GPU_DEPTHBUF")]
 #[doc(alias = "C3D_DEPTHTYPE")]
 pub enum DepthFormat {
@@ -156,9 +572,98 @@ pub enum DepthFormat {
 }
 
 impl DepthFormat {
+    /// Whether this format includes a stencil buffer.
+    pub fn has_stencil(self) -> bool {
+        matches!(self, Self::Depth24Stencil8)
+    }
+
     fn as_raw(self) -> C3D_DEPTHTYPE {
         C3D_DEPTHTYPE {
             __e: self as GPU_DEPTHBUF,
         }
     }
 }
+
+/// Anti-aliasing applied when a [`Target`] is transferred to the screen, by
+/// rendering at a larger size and averaging pixels while downscaling.
+///
+/// Sizes here refer to the framebuffer's axes, which are rotated relative
+/// to the physical screen: the framebuffer's width is the screen's short
+/// (vertical) axis.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[doc(alias = "GX_TRANSFER_SCALE")]
+pub enum AntiAliasing {
+    /// No anti-aliasing.
+    #[default]
+    #[doc(alias = "GX_TRANSFER_SCALE_NO")]
+    None = ctru_sys::GX_TRANSFER_SCALE_NO,
+    /// Render at twice the framebuffer's width and downscale by 2x1.
+    #[doc(alias = "GX_TRANSFER_SCALE_X")]
+    Scale2x1 = ctru_sys::GX_TRANSFER_SCALE_X,
+    /// Render at twice the framebuffer's width and height and downscale by 2x2.
+    #[doc(alias = "GX_TRANSFER_SCALE_XY")]
+    Scale2x2 = ctru_sys::GX_TRANSFER_SCALE_XY,
+}
+
+impl AntiAliasing {
+    /// The factors by which the target's width and height are multiplied,
+    /// relative to the screen's framebuffer.
+    pub fn scale(self) -> (usize, usize) {
+        match self {
+            Self::None => (1, 1),
+            Self::Scale2x1 => (2, 1),
+            Self::Scale2x2 => (2, 2),
+        }
+    }
+}
+
+/// A snapshot of the drawing state which is commonly changed between draws
+/// and render targets: the viewport, scissor test, and face culling.
+///
+/// `citro3d` doesn't provide a way to read back its state, so this reflects
+/// the state as set through the [`Instance`](crate::Instance). Use
+/// [`Instance::render_state`](crate::Instance::render_state) and
+/// [`Instance::set_render_state`](crate::Instance::set_render_state) to save
+/// and restore it, or [`Instance::push_state`](crate::Instance::push_state) and
+/// [`Instance::pop_state`](crate::Instance::pop_state) to do so around a block
+/// of draws.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RenderState {
+    /// The viewport as `[x, y, width, height]`, or `None` if no render target
+    /// has been selected yet.
+    /// See [`Instance::set_viewport`](crate::Instance::set_viewport).
+    pub viewport: Option<[u32; 4]>,
+    /// The scissor mode and rectangle as `[left, top, right, bottom]`, or `None`
+    /// if the scissor test is disabled.
+    /// See [`Instance::set_scissor`](crate::Instance::set_scissor).
+    pub scissor: Option<(ScissorMode, [u32; 4])>,
+    /// The face culling mode.
+    /// See [`Instance::set_cull_face`](crate::Instance::set_cull_face).
+    pub cull_mode: CullMode,
+}
+
+impl Default for RenderState {
+    /// The state of a newly created [`Instance`](crate::Instance).
+    fn default() -> Self {
+        Self {
+            viewport: None,
+            scissor: None,
+            cull_mode: CullMode::BackCcw,
+        }
+    }
+}
+
+/// Whether and how to apply the scissor test. See
+/// [`Instance::set_scissor`](crate::Instance::set_scissor).
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[doc(alias = "GPU_SCISSORMODE")]
+pub enum ScissorMode {
+    /// Disable the scissor test; all pixels may be drawn to.
+    Disable = ctru_sys::GPU_SCISSOR_DISABLE,
+    /// Only draw pixels outside the scissor rectangle.
+    Invert = ctru_sys::GPU_SCISSOR_INVERT,
+    /// Only draw pixels inside the scissor rectangle.
+    Normal = ctru_sys::GPU_SCISSOR_NORMAL,
+}
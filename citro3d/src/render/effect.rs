@@ -0,0 +1,252 @@
+//! Per-fragment operations applied when drawing, based on `<c3d/effect.h>`.
+//! See the corresponding methods on [`Instance`](crate::Instance) to configure them.
+
+/// A comparison function used by the GPU's per-fragment tests, i.e. the depth,
+/// alpha, and stencil tests. The test passes if
+/// `<incoming value> <op> <existing/reference value>`.
+///
+/// This is the single comparison type shared by
+/// [`Instance::set_depth_test`](crate::Instance::set_depth_test),
+/// [`Instance::set_alpha_test`](crate::Instance::set_alpha_test) and
+/// [`Instance::set_stencil_test`](crate::Instance::set_stencil_test).
+/// The early depth test supports fewer comparisons, so it uses
+/// [`EarlyDepthFunc`], which converts to and from this type.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[doc(alias = "GPU_TESTFUNC")]
+#[doc(alias = "CompareFunc")]
+pub enum TestFunc {
+    /// Never pass.
+    Never = ctru_sys::GPU_NEVER,
+    /// Always pass.
+    Always = ctru_sys::GPU_ALWAYS,
+    /// Pass if equal.
+    Equal = ctru_sys::GPU_EQUAL,
+    /// Pass if not equal.
+    NotEqual = ctru_sys::GPU_NOTEQUAL,
+    /// Pass if less than.
+    Less = ctru_sys::GPU_LESS,
+    /// Pass if less than or equal.
+    LessOrEqual = ctru_sys::GPU_LEQUAL,
+    /// Pass if greater than.
+    Greater = ctru_sys::GPU_GREATER,
+    /// Pass if greater than or equal.
+    GreaterOrEqual = ctru_sys::GPU_GEQUAL,
+}
+
+/// The comparison function used for the depth test. This is the same type as
+/// [`TestFunc`], so the two can be used interchangeably.
+pub type DepthFunc = TestFunc;
+
+/// The comparison function used for the early depth test. This is a subset
+/// of [`TestFunc`], as the early depth test only supports these comparisons.
+/// See [`Instance::set_early_depth_test`](crate::Instance::set_early_depth_test).
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[doc(alias = "GPU_EARLYDEPTHFUNC")]
+pub enum EarlyDepthFunc {
+    /// Pass if greater than or equal.
+    GreaterOrEqual = ctru_sys::GPU_EARLYDEPTH_GEQUAL,
+    /// Pass if greater than.
+    Greater = ctru_sys::GPU_EARLYDEPTH_GREATER,
+    /// Pass if less than or equal.
+    LessOrEqual = ctru_sys::GPU_EARLYDEPTH_LEQUAL,
+    /// Pass if less than.
+    Less = ctru_sys::GPU_EARLYDEPTH_LESS,
+}
+
+impl From<EarlyDepthFunc> for TestFunc {
+    fn from(func: EarlyDepthFunc) -> Self {
+        match func {
+            EarlyDepthFunc::GreaterOrEqual => Self::GreaterOrEqual,
+            EarlyDepthFunc::Greater => Self::Greater,
+            EarlyDepthFunc::LessOrEqual => Self::LessOrEqual,
+            EarlyDepthFunc::Less => Self::Less,
+        }
+    }
+}
+
+impl TryFrom<TestFunc> for EarlyDepthFunc {
+    type Error = crate::Error;
+
+    /// Convert a [`TestFunc`], e.g. the one used for the regular depth test,
+    /// into the equivalent early depth test function.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedEarlyDepthFunc`](crate::Error::UnsupportedEarlyDepthFunc)
+    /// if the early depth test doesn't support the comparison.
+    fn try_from(func: TestFunc) -> Result<Self, Self::Error> {
+        match func {
+            TestFunc::GreaterOrEqual => Ok(Self::GreaterOrEqual),
+            TestFunc::Greater => Ok(Self::Greater),
+            TestFunc::LessOrEqual => Ok(Self::LessOrEqual),
+            TestFunc::Less => Ok(Self::Less),
+            TestFunc::Never | TestFunc::Always | TestFunc::Equal | TestFunc::NotEqual => {
+                Err(crate::Error::UnsupportedEarlyDepthFunc(func))
+            }
+        }
+    }
+}
+
+bitflags::bitflags! {
+    /// Which components of the color and depth buffers may be written to
+    /// when drawing.
+    #[doc(alias = "GPU_WRITEMASK")]
+    pub struct WriteMask: u32 {
+        /// Write the red color component.
+        const RED = ctru_sys::GPU_WRITE_RED;
+        /// Write the green color component.
+        const GREEN = ctru_sys::GPU_WRITE_GREEN;
+        /// Write the blue color component.
+        const BLUE = ctru_sys::GPU_WRITE_BLUE;
+        /// Write the alpha color component.
+        const ALPHA = ctru_sys::GPU_WRITE_ALPHA;
+        /// Write the depth buffer.
+        const DEPTH = ctru_sys::GPU_WRITE_DEPTH;
+        /// Write all color components.
+        const COLOR = ctru_sys::GPU_WRITE_COLOR;
+        /// Write all color components and the depth buffer.
+        const ALL = ctru_sys::GPU_WRITE_ALL;
+    }
+}
+
+/// The equation used to combine the source and destination values when blending.
+/// See [`Instance::set_blend`](crate::Instance::set_blend).
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[doc(alias = "GPU_BLENDEQUATION")]
+pub enum BlendEquation {
+    /// `source + destination`
+    Add = ctru_sys::GPU_BLEND_ADD,
+    /// `source - destination`
+    Subtract = ctru_sys::GPU_BLEND_SUBTRACT,
+    /// `destination - source`
+    ReverseSubtract = ctru_sys::GPU_BLEND_REVERSE_SUBTRACT,
+    /// `min(source, destination)`
+    Min = ctru_sys::GPU_BLEND_MIN,
+    /// `max(source, destination)`
+    Max = ctru_sys::GPU_BLEND_MAX,
+}
+
+/// The factor each source or destination value is multiplied by before
+/// being combined with a [`BlendEquation`].
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[doc(alias = "GPU_BLENDFACTOR")]
+pub enum BlendFactor {
+    /// `0`
+    Zero = ctru_sys::GPU_ZERO,
+    /// `1`
+    One = ctru_sys::GPU_ONE,
+    /// The source color.
+    SrcColor = ctru_sys::GPU_SRC_COLOR,
+    /// `1 - ` the source color.
+    OneMinusSrcColor = ctru_sys::GPU_ONE_MINUS_SRC_COLOR,
+    /// The destination color.
+    DstColor = ctru_sys::GPU_DST_COLOR,
+    /// `1 - ` the destination color.
+    OneMinusDstColor = ctru_sys::GPU_ONE_MINUS_DST_COLOR,
+    /// The source alpha.
+    SrcAlpha = ctru_sys::GPU_SRC_ALPHA,
+    /// `1 - ` the source alpha.
+    OneMinusSrcAlpha = ctru_sys::GPU_ONE_MINUS_SRC_ALPHA,
+    /// The destination alpha.
+    DstAlpha = ctru_sys::GPU_DST_ALPHA,
+    /// `1 - ` the destination alpha.
+    OneMinusDstAlpha = ctru_sys::GPU_ONE_MINUS_DST_ALPHA,
+    /// The constant blending color.
+    ConstantColor = ctru_sys::GPU_CONSTANT_COLOR,
+    /// `1 - ` the constant blending color.
+    OneMinusConstantColor = ctru_sys::GPU_ONE_MINUS_CONSTANT_COLOR,
+    /// The constant blending alpha.
+    ConstantAlpha = ctru_sys::GPU_CONSTANT_ALPHA,
+    /// `1 - ` the constant blending alpha.
+    OneMinusConstantAlpha = ctru_sys::GPU_ONE_MINUS_CONSTANT_ALPHA,
+    /// `min(source alpha, 1 - destination alpha)`
+    SrcAlphaSaturate = ctru_sys::GPU_SRC_ALPHA_SATURATE,
+}
+
+/// A bitwise operation combining the source (fragment) color `s` with the
+/// destination (framebuffer) color `d`.
+/// See [`Instance::set_color_logic_op`](crate::Instance::set_color_logic_op).
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[doc(alias = "GPU_LOGICOP")]
+pub enum LogicOp {
+    /// `0`
+    Clear = ctru_sys::GPU_LOGICOP_CLEAR,
+    /// `s & d`
+    And = ctru_sys::GPU_LOGICOP_AND,
+    /// `s & !d`
+    AndReverse = ctru_sys::GPU_LOGICOP_AND_REVERSE,
+    /// `s`, i.e. regular drawing without blending
+    Copy = ctru_sys::GPU_LOGICOP_COPY,
+    /// `1`
+    Set = ctru_sys::GPU_LOGICOP_SET,
+    /// `!s`
+    CopyInverted = ctru_sys::GPU_LOGICOP_COPY_INVERTED,
+    /// `d`
+    Noop = ctru_sys::GPU_LOGICOP_NOOP,
+    /// `!d`
+    Invert = ctru_sys::GPU_LOGICOP_INVERT,
+    /// `!(s & d)`
+    Nand = ctru_sys::GPU_LOGICOP_NAND,
+    /// `s | d`
+    Or = ctru_sys::GPU_LOGICOP_OR,
+    /// `!(s | d)`
+    Nor = ctru_sys::GPU_LOGICOP_NOR,
+    /// `s ^ d`
+    Xor = ctru_sys::GPU_LOGICOP_XOR,
+    /// `!(s ^ d)`
+    Equiv = ctru_sys::GPU_LOGICOP_EQUIV,
+    /// `!s & d`
+    AndInverted = ctru_sys::GPU_LOGICOP_AND_INVERTED,
+    /// `s | !d`
+    OrReverse = ctru_sys::GPU_LOGICOP_OR_REVERSE,
+    /// `!s | d`
+    OrInverted = ctru_sys::GPU_LOGICOP_OR_INVERTED,
+}
+
+/// Which faces of triangles to discard (cull) when drawing.
+///
+/// A triangle's facing is determined by the winding order of its vertices in
+/// screen space, after projection. Triangles wound counter-clockwise (CCW) are
+/// considered front-facing.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[doc(alias = "GPU_CULLMODE")]
+pub enum CullMode {
+    /// Do not cull any triangles.
+    None = ctru_sys::GPU_CULL_NONE,
+    /// Cull front-facing (counter-clockwise) triangles.
+    FrontCcw = ctru_sys::GPU_CULL_FRONT_CCW,
+    /// Cull back-facing (clockwise) triangles. This is the mode set by `citro3d`
+    /// when an [`Instance`](crate::Instance) is created.
+    BackCcw = ctru_sys::GPU_CULL_BACK_CCW,
+}
+
+/// The operation to apply to the stencil buffer value when a fragment passes
+/// or fails the stencil and depth tests.
+/// See [`Instance::set_stencil_op`](crate::Instance::set_stencil_op).
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[doc(alias = "GPU_STENCILOP")]
+pub enum StencilOp {
+    /// Keep the existing value.
+    Keep = ctru_sys::GPU_STENCIL_KEEP,
+    /// Set the value to zero.
+    Zero = ctru_sys::GPU_STENCIL_ZERO,
+    /// Replace the value with the reference value.
+    Replace = ctru_sys::GPU_STENCIL_REPLACE,
+    /// Increment the value, saturating at the maximum value.
+    Increment = ctru_sys::GPU_STENCIL_INCR,
+    /// Decrement the value, saturating at zero.
+    Decrement = ctru_sys::GPU_STENCIL_DECR,
+    /// Bitwise invert the value.
+    Invert = ctru_sys::GPU_STENCIL_INVERT,
+    /// Increment the value, wrapping to zero on overflow.
+    IncrementWrap = ctru_sys::GPU_STENCIL_INCR_WRAP,
+    /// Decrement the value, wrapping to the maximum value on underflow.
+    DecrementWrap = ctru_sys::GPU_STENCIL_DECR_WRAP,
+}
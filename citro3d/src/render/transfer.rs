@@ -1,39 +1,100 @@
-use citro3d_sys::{GX_TRANSFER_IN_FORMAT, GX_TRANSFER_OUT_FORMAT};
-use ctru_sys::GX_TRANSFER_FORMAT;
+use citro3d_sys::{
+    GX_TRANSFER_FLIP_VERT, GX_TRANSFER_IN_FORMAT, GX_TRANSFER_OUT_FORMAT, GX_TRANSFER_OUT_TILED,
+    GX_TRANSFER_RAW_COPY, GX_TRANSFER_SCALING,
+};
+use ctru_sys::{GX_TRANSFER_FORMAT, GX_TRANSFER_SCALE};
 
-use super::ColorFormat;
+use super::{AntiAliasing, ColorFormat};
 
-/// Control flags for a GX data transfer.
-#[derive(Default, Clone, Copy)]
-pub struct Flags(u32);
+/// Control flags for a GX display transfer, which copies a render target's
+/// color buffer to a framebuffer, converting its format and layout.
+/// See [`Target::transfer_to_screen`](super::Target::transfer_to_screen).
+///
+/// Each setting should be applied at most once. The defaults are an `RGBA8`
+/// input and output, without flipping or scaling, converting the GPU's tiled
+/// layout to the linear layout of the screens' framebuffers.
+///
+/// # Example
+///
+/// ```
+/// # let _runner = test_runner::GdbRunner::default();
+/// # use citro3d::render::{AntiAliasing, ColorFormat, TransferFlags, TransferFormat};
+/// let flags = TransferFlags::default()
+///     .in_format(ColorFormat::RGBA8.into())
+///     .out_format(TransferFormat::RGB8)
+///     .scaling(AntiAliasing::Scale2x2)
+///     .flip_vertical(true);
+/// ```
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransferFlags(u32);
 
-impl Flags {
+impl TransferFlags {
     /// Set the input format of the data transfer.
     #[must_use]
-    pub fn in_format(self, fmt: Format) -> Self {
+    pub fn in_format(self, fmt: TransferFormat) -> Self {
         Self(self.0 | GX_TRANSFER_IN_FORMAT(fmt as GX_TRANSFER_FORMAT))
     }
 
     /// Set the output format of the data transfer.
     #[must_use]
-    pub fn out_format(self, fmt: Format) -> Self {
+    pub fn out_format(self, fmt: TransferFormat) -> Self {
         Self(self.0 | GX_TRANSFER_OUT_FORMAT(fmt as GX_TRANSFER_FORMAT))
     }
 
+    /// Set the downscaling applied during the data transfer.
+    #[must_use]
+    pub fn scaling(self, anti_aliasing: AntiAliasing) -> Self {
+        Self(self.0 | GX_TRANSFER_SCALING(anti_aliasing as GX_TRANSFER_SCALE))
+    }
+
+    /// Flip the image vertically during the transfer.
+    #[doc(alias = "GX_TRANSFER_FLIP_VERT")]
+    #[must_use]
+    pub fn flip_vertical(self, flip: bool) -> Self {
+        Self(self.0 | GX_TRANSFER_FLIP_VERT(flip))
+    }
+
+    /// Keep the output in the GPU's tiled layout (8x8 pixel blocks) instead
+    /// of converting it to a linear layout, e.g. to transfer into a texture.
+    #[doc(alias = "GX_TRANSFER_OUT_TILED")]
+    #[must_use]
+    pub fn tiled_output(self, tiled: bool) -> Self {
+        Self(self.0 | GX_TRANSFER_OUT_TILED(tiled))
+    }
+
+    /// Copy the data as-is, without any format or layout conversion.
+    #[doc(alias = "GX_TRANSFER_RAW_COPY")]
+    #[must_use]
+    pub fn raw_copy(self, raw_copy: bool) -> Self {
+        Self(self.0 | GX_TRANSFER_RAW_COPY(raw_copy))
+    }
+
+    /// The raw flags, as passed to `GX_DisplayTransfer`.
     #[must_use]
     pub fn bits(self) -> u32 {
         self.0
     }
+
+    /// The factors by which the input is downscaled, see [`AntiAliasing::scale`].
+    pub(crate) fn scale(self) -> (usize, usize) {
+        match (self.0 >> 24) & 0x3 {
+            ctru_sys::GX_TRANSFER_SCALE_X => AntiAliasing::Scale2x1,
+            ctru_sys::GX_TRANSFER_SCALE_XY => AntiAliasing::Scale2x2,
+            _ => AntiAliasing::None,
+        }
+        .scale()
+    }
 }
 
 /// The color format to use when transferring data to/from the GPU.
 ///
 /// NOTE: this a distinct type from [`ColorFormat`] because they are not implicitly
-/// convertible to one another. Use [`From::from`] to get the [`Format`] corresponding
-/// to a given [`ColorFormat`].
+/// convertible to one another. Use [`From::from`] to get the [`TransferFormat`]
+/// corresponding to a given [`ColorFormat`].
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[doc(alias = "GX_TRANSFER_FORMAT")]
-pub enum Format {
+pub enum TransferFormat {
     /// 8-bit Red + 8-bit Green + 8-bit Blue + 8-bit Alpha.
     RGBA8 = ctru_sys::GX_TRANSFER_FMT_RGBA8,
     /// 8-bit Red + 8-bit Green + 8-bit Blue.
@@ -46,7 +107,7 @@ pub enum Format {
     RGBA4 = ctru_sys::GX_TRANSFER_FMT_RGBA4,
 }
 
-impl From<ColorFormat> for Format {
+impl From<ColorFormat> for TransferFormat {
     fn from(color_fmt: ColorFormat) -> Self {
         match color_fmt {
             ColorFormat::RGBA8 => Self::RGBA8,
@@ -4,13 +4,13 @@
 //! For more details about the PICA200 compiler / shader language, see
 //! documentation for <https://github.com/devkitPro/picasso>.
 
-use std::error::Error;
 use std::ffi::CString;
+use std::fmt;
 use std::marker::PhantomPinned;
 use std::mem::MaybeUninit;
 use std::pin::Pin;
 use std::ptr::NonNull;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use crate::uniform;
 
@@ -87,12 +87,90 @@ impl Program {
         }
     }
 
-    /// Get the index of a uniform by name.
+    /// Create a new shader program from a vertex shader and a geometry shader.
+    ///
+    /// `stride` is the number of vertex shader output registers the geometry
+    /// shader receives per vertex, and `mode` selects how the geometry shader
+    /// is run (see [`GeometryShaderMode`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// * the shader program cannot be initialized
+    /// * either input shader is not of the expected [`Type`] or is otherwise invalid
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::shader::{GeometryShaderMode, Library, Program};
+    /// # static SHADER_BYTES: &[u8] = &[];
+    /// let library = Library::from_bytes(SHADER_BYTES).unwrap();
+    /// let program = Program::with_geometry(
+    ///     library.get(0).unwrap(),
+    ///     library.get(1).unwrap(),
+    ///     2,
+    ///     GeometryShaderMode::Normal,
+    /// )
+    /// .unwrap();
+    /// ```
+    #[doc(alias = "shaderProgramSetGsh")]
+    #[doc(alias = "shaderProgramSetGshMode")]
+    pub fn with_geometry(
+        vertex_shader: Entrypoint,
+        geometry_shader: Entrypoint,
+        stride: u8,
+        mode: GeometryShaderMode,
+    ) -> Result<Self, ctru::Error> {
+        let mut program = Self::new(vertex_shader)?;
+        program.set_geometry_shader(geometry_shader, stride)?;
+        program.set_geometry_shader_mode(mode)?;
+        Ok(program)
+    }
+
+    /// Set the operating mode of the program's geometry shader. This only has
+    /// an effect if a geometry shader was set with [`Self::set_geometry_shader`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mode could not be set, e.g. because the program
+    /// has no geometry shader.
+    #[doc(alias = "shaderProgramSetGshMode")]
+    pub fn set_geometry_shader_mode(
+        &mut self,
+        mode: GeometryShaderMode,
+    ) -> Result<(), ctru::Error> {
+        let ret = unsafe { ctru_sys::shaderProgramSetGshMode(&mut self.program, mode.into()) };
+
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(ctru::Error::from(ret))
+        }
+    }
+
+    /// Get the index of a uniform by name, from the vertex shader's uniform table.
+    /// The resulting index can be passed directly to
+    /// [`Instance::bind_vertex_uniform`](crate::Instance::bind_vertex_uniform),
+    /// so it stays correct even if the shader's registers change when recompiled.
     ///
     /// # Errors
     ///
     /// * If the given `name` contains a null byte
     /// * If a uniform with the given `name` could not be found
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::shader::{Library, Program};
+    /// # static SHADER_BYTES: &[u8] = citro3d::macros::include_shader!("../examples/assets/vshader.pica");
+    /// let library = Library::from_bytes(SHADER_BYTES).unwrap();
+    /// let program = Program::new(library.get(0).unwrap()).unwrap();
+    ///
+    /// let projection = program.get_uniform("projection").unwrap();
+    /// assert!(program.get_uniform("nonexistent").is_err());
+    /// ```
     #[doc(alias = "shaderInstanceGetUniformLocation")]
     pub fn get_uniform(&self, name: &str) -> crate::Result<uniform::Index> {
         let vertex_instance = self.program.vertexShader;
@@ -113,6 +191,31 @@ impl Program {
         }
     }
 
+    /// Get the index of a uniform by name, from the vertex shader's uniform table,
+    /// or `None` if it could not be found (including if `name` contains a null
+    /// byte). This is the same as [`Self::get_uniform`], for when the reason
+    /// for a failed lookup doesn't matter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::shader::{Library, Program};
+    /// # static SHADER_BYTES: &[u8] = citro3d::macros::include_shader!("../examples/assets/vshader.pica");
+    /// let library = Library::from_bytes(SHADER_BYTES).unwrap();
+    /// let program = Program::new(library.get(0).unwrap()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     program.uniform_location("projection"),
+    ///     program.get_uniform("projection").ok()
+    /// );
+    /// assert_eq!(program.uniform_location("nonexistent"), None);
+    /// ```
+    #[doc(alias = "shaderInstanceGetUniformLocation")]
+    pub fn uniform_location(&self, name: &str) -> Option<uniform::Index> {
+        self.get_uniform(name).ok()
+    }
+
     pub(crate) fn as_raw(self: &Pin<Arc<Self>>) -> *const ctru_sys::shaderProgram_s {
         &self.program
     }
@@ -132,7 +235,7 @@ impl Drop for Program {
 
 /// The type of a shader.
 #[repr(u32)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Type {
     /// A vertex shader.
     Vertex = ctru_sys::GPU_VERTEX_SHADER,
@@ -146,6 +249,33 @@ impl From<Type> for u32 {
     }
 }
 
+/// The operating mode of a geometry shader, i.e. how its input vertices are
+/// grouped before being passed to it.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[doc(alias = "geoShaderMode")]
+pub enum GeometryShaderMode {
+    /// Each primitive's vertices are passed to the shader, e.g. to generate
+    /// billboards from points.
+    #[doc(alias = "GSH_NORMAL")]
+    Normal = ctru_sys::GSH_NORMAL,
+    /// Particle system mode.
+    #[doc(alias = "GSH_PARTICLE")]
+    Particle = ctru_sys::GSH_PARTICLE,
+    /// Loop subdivision of input patches.
+    #[doc(alias = "GSH_SUBDIVISION_LOOP")]
+    SubdivisionLoop = ctru_sys::GSH_SUBDIVISION_LOOP,
+    /// Catmull-Clark subdivision of input patches.
+    #[doc(alias = "GSH_SUBDIVISION_CATMULL_CLARK")]
+    SubdivisionCatmullClark = ctru_sys::GSH_SUBDIVISION_CATMULL_CLARK,
+}
+
+impl From<GeometryShaderMode> for ctru_sys::geoShaderMode {
+    fn from(value: GeometryShaderMode) -> Self {
+        value as _
+    }
+}
+
 /// A PICA200 Shader Library (commonly called DVLB). This can be comprised of
 /// one or more [`Entrypoint`]s, but most commonly has one vertex shader and an
 /// optional geometry shader.
@@ -161,14 +291,37 @@ unsafe impl Send for Library {}
 unsafe impl Sync for Library {}
 
 impl Library {
-    /// Parse a new shader library from input bytes.
+    /// Parse a new shader library from the bytes of a compiled shader binary
+    /// (`.shbin`), e.g. one produced by [`include_shader!`](crate::macros::include_shader)
+    /// or loaded at runtime.
+    ///
+    /// Individual shaders can then be selected with [`Self::get`] and used
+    /// to create a [`Program`].
     ///
     /// # Errors
     ///
-    /// An error is returned if the input data does not have an alignment of 4
-    /// (cannot be safely converted to `&[u32]`).
+    /// * [`Error::InvalidAlignment`](crate::Error::InvalidAlignment) if the input
+    ///   data is not aligned to 4 bytes, since it is read as `&[u32]`.
+    /// * [`Error::InvalidSize`](crate::Error::InvalidSize) if the input length is
+    ///   not a multiple of 4 bytes.
+    /// * [`Error::FailedToInitialize`](crate::Error::FailedToInitialize) if the
+    ///   data could not be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::shader::Library;
+    /// let words = [0_u32; 4];
+    /// let bytes: &[u8] = bytemuck::cast_slice(&words);
+    ///
+    /// assert!(matches!(
+    ///     Library::from_bytes(&bytes[1..9]),
+    ///     Err(citro3d::Error::InvalidAlignment)
+    /// ));
+    /// ```
     #[doc(alias = "DVLB_ParseFile")]
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+    pub fn from_bytes(bytes: &[u8]) -> crate::Result<Self> {
         let aligned: &[u32] = bytemuck::try_cast_slice(bytes)?;
         let lib = unsafe {
             ctru_sys::DVLB_ParseFile(
@@ -179,7 +332,7 @@ impl Library {
                 aligned.len().try_into()?,
             )
         };
-        let lib = NonNull::new(lib).ok_or(Box::new(super::Error::FailedToInitialize))?;
+        let lib = NonNull::new(lib).ok_or(crate::Error::FailedToInitialize)?;
         Ok(Self(lib))
     }
 
@@ -197,6 +350,22 @@ impl Library {
     }
 
     /// Get the [`Entrypoint`] at the given index, if present.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::shader::{Library, Program, Type};
+    /// # static SHADER_BYTES: &[u8] = citro3d::macros::include_shader!("../examples/assets/vshader.pica");
+    /// let library = Library::from_bytes(SHADER_BYTES).unwrap();
+    /// assert_eq!(library.len(), 1);
+    ///
+    /// let vertex_shader = library.get(0).unwrap();
+    /// assert_eq!(vertex_shader.shader_type(), Type::Vertex);
+    /// assert!(library.get(1).is_none());
+    ///
+    /// let program = Program::new(vertex_shader).unwrap();
+    /// ```
     #[must_use]
     pub fn get(&self, index: usize) -> Option<Entrypoint> {
         if index < self.len() {
@@ -208,6 +377,12 @@ impl Library {
             None
         }
     }
+
+    /// Iterate over all the [`Entrypoint`]s in this shader library, in order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Entrypoint> + '_ {
+        (0..self.len()).map(|i| self.get(i).unwrap())
+    }
+
     pub fn get_uniform(&self, name: &str) -> Option<uniform::Index> {
         let dvle = unsafe { (*self.0.as_ptr()).DVLE.cast_const() };
         assert!(!dvle.is_null(), "dvle should not be null");
@@ -237,6 +412,69 @@ impl Drop for Library {
     }
 }
 
+/// A compiled shader binary embedded in the program, which is parsed into a
+/// [`Library`] the first time it is used.
+///
+/// Parsing a shader binary (`DVLB_ParseFile`) allocates, so it can't happen at
+/// compile time. Instead, the compiled bytes are stored as a `&'static [u8]`
+/// and parsed at runtime on the first call to [`Self::library`]; later calls
+/// return the same [`Library`]. This means an `EmbeddedLibrary` can be stored
+/// in a `static`, and is usually created with
+/// [`include_shader_library!`](crate::macros::include_shader_library).
+///
+/// # Example
+///
+/// ```
+/// # let _runner = test_runner::GdbRunner::default();
+/// use citro3d::macros::include_shader_library;
+/// use citro3d::shader::{EmbeddedLibrary, Program};
+///
+/// static SHADER: EmbeddedLibrary = include_shader_library!("../examples/assets/vshader.pica");
+///
+/// let program = Program::new(SHADER.library().get(0).unwrap()).unwrap();
+/// ```
+pub struct EmbeddedLibrary {
+    bytes: &'static [u8],
+    library: OnceLock<Library>,
+}
+
+impl EmbeddedLibrary {
+    /// Wrap the given compiled shader bytes, without parsing them yet. The bytes
+    /// should be aligned to 4 bytes, as those produced by
+    /// [`include_shader!`](crate::macros::include_shader) are.
+    pub const fn new(bytes: &'static [u8]) -> Self {
+        Self {
+            bytes,
+            library: OnceLock::new(),
+        }
+    }
+
+    /// The compiled shader bytes.
+    pub fn bytes(&self) -> &'static [u8] {
+        self.bytes
+    }
+
+    /// Get the parsed shader library, parsing it if this is the first use.
+    ///
+    /// # Panics
+    ///
+    /// If the bytes could not be parsed, see [`Library::from_bytes`].
+    pub fn library(&self) -> &Library {
+        self.library.get_or_init(|| {
+            Library::from_bytes(self.bytes).expect("embedded shader library should be valid")
+        })
+    }
+}
+
+impl fmt::Debug for EmbeddedLibrary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmbeddedLibrary")
+            .field("len", &self.bytes.len())
+            .field("library", &self.library.get())
+            .finish()
+    }
+}
+
 /// A shader library entrypoint (also called DVLE). This represents either a
 /// vertex or a geometry shader.
 #[derive(Clone, Copy)]
@@ -246,7 +484,24 @@ pub struct Entrypoint<'lib> {
 }
 
 impl<'lib> Entrypoint<'lib> {
+    /// Whether this is a vertex or a geometry shader.
+    #[doc(alias = "DVLE_type")]
+    pub fn shader_type(self) -> Type {
+        match unsafe { (*self.ptr).type_ } {
+            ctru_sys::GEOMETRY_SHDR => Type::Geometry,
+            _ => Type::Vertex,
+        }
+    }
+
     fn as_raw(self) -> *mut ctru_sys::DVLE_s {
         self.ptr
     }
 }
+
+impl fmt::Debug for Entrypoint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entrypoint")
+            .field("shader_type", &self.shader_type())
+            .finish_non_exhaustive()
+    }
+}
@@ -7,6 +7,20 @@ use bitflags::bitflags;
 
 /// A texture combiner, also called a "texture environment" (hence the struct name).
 /// See also [`texenv.h` documentation](https://oreo639.github.io/citro3d/texenv_8h.html).
+///
+/// # Example
+///
+/// ```
+/// # let _runner = test_runner::GdbRunner::default();
+/// # use citro3d::texenv::{CombineFunc, Mode, Source, Stage};
+/// # let gfx = ctru::services::gfx::Gfx::new().unwrap();
+/// # let mut instance = citro3d::Instance::new(&gfx).unwrap();
+/// // Modulate (multiply) the texture bound to unit 0 by the vertex color
+/// instance
+///     .texenv(Stage::new(0).unwrap())
+///     .src(Mode::BOTH, Source::Texture0, Some(Source::PrimaryColor), None)
+///     .func(Mode::BOTH, CombineFunc::Modulate);
+/// ```
 #[doc(alias = "C3D_TexEnv")]
 #[derive(Debug)]
 pub struct TexEnv(NonNull<citro3d_sys::C3D_TexEnv>);
@@ -79,6 +93,65 @@ impl TexEnv {
 
         self
     }
+
+    /// Configure which components of the RGB source operands are used.
+    /// Each operand corresponds to the [`Source`] of the same position
+    /// set with [`Self::src`].
+    ///
+    /// Operands which are not specified default to [`RgbOp::SrcColor`].
+    #[doc(alias = "C3D_TexEnvOpRgb")]
+    pub fn op_rgb(&mut self, op0: RgbOp, op1: Option<RgbOp>, op2: Option<RgbOp>) -> &mut Self {
+        unsafe {
+            citro3d_sys::C3D_TexEnvOpRgb(
+                self.0.as_ptr(),
+                op0 as _,
+                op1.unwrap_or(RgbOp::SrcColor) as _,
+                op2.unwrap_or(RgbOp::SrcColor) as _,
+            );
+        }
+        self
+    }
+
+    /// Configure which components of the alpha source operands are used.
+    /// Each operand corresponds to the [`Source`] of the same position
+    /// set with [`Self::src`].
+    ///
+    /// Operands which are not specified default to [`AlphaOp::SrcAlpha`].
+    #[doc(alias = "C3D_TexEnvOpAlpha")]
+    pub fn op_alpha(
+        &mut self,
+        op0: AlphaOp,
+        op1: Option<AlphaOp>,
+        op2: Option<AlphaOp>,
+    ) -> &mut Self {
+        unsafe {
+            citro3d_sys::C3D_TexEnvOpAlpha(
+                self.0.as_ptr(),
+                op0 as _,
+                op1.unwrap_or(AlphaOp::SrcAlpha) as _,
+                op2.unwrap_or(AlphaOp::SrcAlpha) as _,
+            );
+        }
+        self
+    }
+
+    /// Set the constant color used by [`Source::Constant`], packed as `0xAABBGGRR`.
+    #[doc(alias = "C3D_TexEnvColor")]
+    pub fn color(&mut self, color: u32) -> &mut Self {
+        unsafe {
+            citro3d_sys::C3D_TexEnvColor(self.0.as_ptr(), color);
+        }
+        self
+    }
+
+    /// Set the factor the output of the combiner is multiplied by.
+    #[doc(alias = "C3D_TexEnvScale")]
+    pub fn scale(&mut self, mode: Mode, scale: Scale) -> &mut Self {
+        unsafe {
+            citro3d_sys::C3D_TexEnvScale(self.0.as_ptr(), mode.bits() as _, scale as _);
+        }
+        self
+    }
 }
 
 bitflags! {
@@ -108,6 +181,8 @@ pub enum Source {
     Texture1 = ctru_sys::GPU_TEXTURE1,
     Texture2 = ctru_sys::GPU_TEXTURE2,
     Texture3 = ctru_sys::GPU_TEXTURE3,
+    /// The combiner buffer, which holds the output of an earlier stage.
+    /// See [`Instance::set_texenv_buffer_update`](crate::Instance::set_texenv_buffer_update).
     PreviousBuffer = ctru_sys::GPU_PREVIOUS_BUFFER,
     Constant = ctru_sys::GPU_CONSTANT,
     Previous = ctru_sys::GPU_PREVIOUS,
@@ -131,6 +206,51 @@ pub enum CombineFunc {
     // Dot3Rgba = ctru_sys::GPU_DOT3_RGBA,
 }
 
+/// The components of a [`Source`] used as an RGB operand.
+#[doc(alias = "GPU_TEVOP_RGB")]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy)]
+#[repr(u32)]
+pub enum RgbOp {
+    SrcColor = ctru_sys::GPU_TEVOP_RGB_SRC_COLOR,
+    OneMinusSrcColor = ctru_sys::GPU_TEVOP_RGB_ONE_MINUS_SRC_COLOR,
+    SrcAlpha = ctru_sys::GPU_TEVOP_RGB_SRC_ALPHA,
+    OneMinusSrcAlpha = ctru_sys::GPU_TEVOP_RGB_ONE_MINUS_SRC_ALPHA,
+    SrcR = ctru_sys::GPU_TEVOP_RGB_SRC_R,
+    OneMinusSrcR = ctru_sys::GPU_TEVOP_RGB_ONE_MINUS_SRC_R,
+    SrcG = ctru_sys::GPU_TEVOP_RGB_SRC_G,
+    OneMinusSrcG = ctru_sys::GPU_TEVOP_RGB_ONE_MINUS_SRC_G,
+    SrcB = ctru_sys::GPU_TEVOP_RGB_SRC_B,
+    OneMinusSrcB = ctru_sys::GPU_TEVOP_RGB_ONE_MINUS_SRC_B,
+}
+
+/// The component of a [`Source`] used as an alpha operand.
+#[doc(alias = "GPU_TEVOP_A")]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy)]
+#[repr(u32)]
+pub enum AlphaOp {
+    SrcAlpha = ctru_sys::GPU_TEVOP_A_SRC_ALPHA,
+    OneMinusSrcAlpha = ctru_sys::GPU_TEVOP_A_ONE_MINUS_SRC_ALPHA,
+    SrcR = ctru_sys::GPU_TEVOP_A_SRC_R,
+    OneMinusSrcR = ctru_sys::GPU_TEVOP_A_ONE_MINUS_SRC_R,
+    SrcG = ctru_sys::GPU_TEVOP_A_SRC_G,
+    OneMinusSrcG = ctru_sys::GPU_TEVOP_A_ONE_MINUS_SRC_G,
+    SrcB = ctru_sys::GPU_TEVOP_A_SRC_B,
+    OneMinusSrcB = ctru_sys::GPU_TEVOP_A_ONE_MINUS_SRC_B,
+}
+
+/// The factor to multiply a [`TexEnv`]'s output by.
+#[doc(alias = "GPU_TEVSCALE")]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy)]
+#[repr(u32)]
+pub enum Scale {
+    X1 = ctru_sys::GPU_TEVSCALE_1,
+    X2 = ctru_sys::GPU_TEVSCALE_2,
+    X4 = ctru_sys::GPU_TEVSCALE_4,
+}
+
 /// A texture combination stage identifier. This index doubles as the order
 /// in which texture combinations will be applied.
 // (I think?)
@@ -142,4 +262,13 @@ impl Stage {
     pub fn new(index: usize) -> Option<Self> {
         (index < 6).then_some(Self(index))
     }
+
+    /// Whether this stage's output can be written to the combiner buffer.
+    /// Only stages 0 to 3 can update the buffer.
+    pub fn can_update_buffer(self) -> bool {
+        self.0 < BUFFER_UPDATE_STAGES
+    }
 }
+
+/// The number of stages (starting from stage 0) which can update the combiner buffer.
+pub(crate) const BUFFER_UPDATE_STAGES: usize = 4;
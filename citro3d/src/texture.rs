@@ -1,7 +1,54 @@
+//! Texture support. Textures are created with [`Tex::new`], filled with pixel
+//! data with [`Tex::upload`], and bound to a texture unit with [`Tex::bind`] so
+//! they can be sampled by a [`TexEnv`](crate::texenv::TexEnv) stage.
+
 use std::ptr::NonNull;
 
 use citro3d_sys::C3D_TexCube;
 
+/// One of the texture units a [`Tex`] can be bound to with [`Tex::bind`].
+///
+/// There are [`MAX_TEXTURE_UNITS`](crate::limits::MAX_TEXTURE_UNITS) units
+/// which sample bound textures. The fourth texture source of the
+/// [`TexEnv`](crate::texenv::TexEnv) stages is the procedural texture unit,
+/// see [`proctex`](crate::proctex).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TexUnit {
+    /// Texture unit 0, the only unit which can sample cube maps and shadow
+    /// textures.
+    Unit0,
+    /// Texture unit 1.
+    Unit1,
+    /// Texture unit 2.
+    Unit2,
+}
+
+impl TexUnit {
+    /// All the texture units, in order.
+    pub const ALL: [Self; crate::limits::MAX_TEXTURE_UNITS] =
+        [Self::Unit0, Self::Unit1, Self::Unit2];
+
+    /// The unit's index, as used by `C3D_TexBind`.
+    pub fn id(self) -> i32 {
+        self as i32
+    }
+
+    /// The [`TexEnv`](crate::texenv::TexEnv) source which samples this unit.
+    pub fn source(self) -> crate::texenv::Source {
+        match self {
+            Self::Unit0 => crate::texenv::Source::Texture0,
+            Self::Unit1 => crate::texenv::Source::Texture1,
+            Self::Unit2 => crate::texenv::Source::Texture2,
+        }
+    }
+}
+
+impl From<TexUnit> for crate::texenv::Source {
+    fn from(unit: TexUnit) -> Self {
+        unit.source()
+    }
+}
+
 #[doc(alias = "GPU_TEXTURE_MODE_PARAM")]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u32)]
@@ -60,9 +107,11 @@ pub enum TexFormat {
     L4 = ctru_sys::GPU_L4,
     /// 4-bit Alpha
     A4 = ctru_sys::GPU_A4,
-    /// ETC1 texture compression
+    /// ETC1 texture compression. Each 4x4 pixel block is stored as 8 bytes,
+    /// with the blocks in the same tiled order as uncompressed formats.
     Etc1 = ctru_sys::GPU_ETC1,
-    /// ETC1 texture compression + 4-bit Alpha
+    /// ETC1 texture compression + 4-bit Alpha. Each 4x4 pixel block is stored
+    /// as 8 bytes of alpha followed by 8 bytes of ETC1 color data.
     Etc1A4 = ctru_sys::GPU_ETC1A4,
 }
 
@@ -81,6 +130,15 @@ impl TexFormat {
             TexFormat::L4 | TexFormat::A4 | TexFormat::Etc1 => 4,
         }
     }
+
+    /// Whether the format is block-compressed ([`TexFormat::Etc1`] or
+    /// [`TexFormat::Etc1A4`]). Compressed data is made of 4x4 pixel blocks.
+    /// Texture dimensions are always powers of two of at least 8 (see
+    /// [`Tex::validate_dimensions`]), and so are those of every mipmap level,
+    /// so any texture size is a whole number of blocks.
+    pub fn is_compressed(&self) -> bool {
+        matches!(self, TexFormat::Etc1 | TexFormat::Etc1A4)
+    }
 }
 
 impl TryFrom<ctru_sys::GPU_TEXCOLOR> for TexFormat {
@@ -117,13 +175,17 @@ pub enum TextureFilterParam {
     Linear = ctru_sys::GPU_LINEAR,
 }
 
+/// How texture coordinates outside of `0.0..=1.0` are handled when sampling a
+/// texture. See [`Tex::set_wrap`].
 #[doc(alias = "GPU_TEXTURE_WRAP_PARAM")]
+#[doc(alias = "WrapMode")]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u32)]
 pub enum TextureWrapParam {
     /// Clamps to edge.
     ClampToEdge = ctru_sys::GPU_CLAMP_TO_EDGE,
-    /// Clamps to border.
+    /// Clamps to border, i.e. samples outside the texture use the color set
+    /// by [`Tex::set_border_color`].
     ClampToBorder = ctru_sys::GPU_CLAMP_TO_BORDER,
     /// Repeats texture.
     Repeat = ctru_sys::GPU_REPEAT,
@@ -131,13 +193,58 @@ pub enum TextureWrapParam {
     MirroredRepeat = ctru_sys::GPU_MIRRORED_REPEAT,
 }
 
+/// A face of a texture. Cube map textures have six faces, while 2D textures
+/// only have a single face, [`Face::TEX_2D`].
+#[doc(alias = "GPU_TEXFACE")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Face {
+    /// The +X face of a cube map.
+    PositiveX = ctru_sys::GPU_POSITIVE_X,
+    /// The -X face of a cube map.
+    NegativeX = ctru_sys::GPU_NEGATIVE_X,
+    /// The +Y face of a cube map.
+    PositiveY = ctru_sys::GPU_POSITIVE_Y,
+    /// The -Y face of a cube map.
+    NegativeY = ctru_sys::GPU_NEGATIVE_Y,
+    /// The +Z face of a cube map.
+    PositiveZ = ctru_sys::GPU_POSITIVE_Z,
+    /// The -Z face of a cube map.
+    NegativeZ = ctru_sys::GPU_NEGATIVE_Z,
+}
+
+impl Face {
+    /// The only face of a 2D texture. This has the same value as [`Face::PositiveX`].
+    #[doc(alias = "GPU_TEXFACE_2D")]
+    pub const TEX_2D: Self = Self::PositiveX;
+}
+
+/// Which memory a texture's pixel data is allocated in.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum MemoryRegion {
+    /// Linear (CPU-accessible) memory, allocated with `linearAlloc`. This is
+    /// shared with e.g. vertex buffers.
+    #[default]
+    Linear,
+    /// Dedicated video memory, allocated with `vramAlloc`. VRAM is faster for
+    /// the GPU to access but much smaller (6 MiB), and can't be written by the
+    /// CPU directly, so uploads go through a DMA transfer.
+    #[doc(alias = "C3D_TexInitVRAM")]
+    Vram,
+    /// Try to allocate in [`MemoryRegion::Vram`], falling back to
+    /// [`MemoryRegion::Linear`] if VRAM is exhausted. Use
+    /// [`Tex::memory_region`] to check where the texture ended up.
+    PreferVram,
+}
+
 #[doc(alias = "C3D_TexInitParams")]
 pub struct TexParams {
-    use_vram: bool,
+    memory: MemoryRegion,
     width: u16,
     height: u16,
     format: TexFormat,
     kind: TexKind,
+    max_level: u8,
     cube: Option<C3D_TexCube>,
 }
 
@@ -145,18 +252,45 @@ impl TexParams {
     /// Parameters for 2d texture in rgba8 format using CPU memory
     pub fn new_2d(width: u16, height: u16) -> Self {
         Self {
-            use_vram: false,
+            memory: MemoryRegion::Linear,
             width,
             height,
             format: TexFormat::Rgba8,
             kind: TexKind::Tex2d,
+            max_level: 0,
             cube: None,
         }
     }
 
+    /// Parameters for a cube map texture in rgba8 format using CPU memory, with
+    /// six square faces of `size` by `size` pixels.
+    ///
+    /// Cube maps must be bound to texture unit 0, and are sampled using the
+    /// 3-component direction from the `texcoord0` and `texcoord0w` outputs of
+    /// the vertex shader.
+    pub fn new_cube(size: u16) -> Self {
+        Self {
+            kind: TexKind::CubeMap,
+            cube: Some(C3D_TexCube {
+                data: [std::ptr::null_mut(); 6],
+            }),
+            ..Self::new_2d(size, size)
+        }
+    }
+
     /// Set whether to use vram for storing pixels
     pub fn use_vram(mut self, v: bool) -> Self {
-        self.use_vram = v;
+        self.memory = if v {
+            MemoryRegion::Vram
+        } else {
+            MemoryRegion::Linear
+        };
+        self
+    }
+
+    /// Set which memory to allocate the pixel data in.
+    pub fn memory_region(mut self, region: MemoryRegion) -> Self {
+        self.memory = region;
         self
     }
 
@@ -180,8 +314,29 @@ impl TexParams {
         self.format = fmt;
         self
     }
+
+    /// Set the number of mipmap levels to allocate in addition to the
+    /// full-size image, e.g. `3` for a 64x64 texture allocates 32x32, 16x16 and
+    /// 8x8 levels. The default of `0` creates a texture without mipmaps.
+    ///
+    /// Each level is half the size of the previous one, and levels smaller
+    /// than 8x8 are not supported, so `level` is clamped accordingly.
+    /// See [`Tex::generate_mipmap`] to fill the levels.
+    pub fn max_level(mut self, level: u8) -> Self {
+        self.max_level = level;
+        self
+    }
+
+    /// The largest mipmap level that fits the texture size, since the smallest
+    /// supported level is 8x8.
+    fn clamped_max_level(&self) -> u8 {
+        let min_size = self.width.min(self.height).max(8);
+        let possible = (min_size.ilog2() - 3) as u8;
+        self.max_level.min(possible)
+    }
 }
 
+/// A texture which can be sampled by the GPU.
 #[doc(alias = "C3D_Tex")]
 #[derive(Debug)]
 pub struct Tex(NonNull<citro3d_sys::C3D_Tex>);
@@ -194,11 +349,69 @@ impl Tex {
     ///
     /// ```
     /// # let _runner = test_runner::GdbRunner::default();
-    /// # use citro3d::texture::{Tex, TexParams};
-    /// let tex = Tex::new(TexParams::new_2d(480, 320).use_vram(true));
+    /// # use citro3d::texture::{MemoryRegion, Tex, TexParams};
+    /// let tex = Tex::new(TexParams::new_2d(512, 256).use_vram(true));
+    ///
+    /// let tex = Tex::new(TexParams::new_2d(256, 256).memory_region(MemoryRegion::PreferVram))
+    ///     .unwrap();
+    /// assert_ne!(tex.memory_region(), MemoryRegion::PreferVram);
     /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidTextureDimensions`](crate::Error::InvalidTextureDimensions)
+    /// if the size is not supported (see [`Tex::validate_dimensions`]), or
+    /// [`Error::TextureCreationFailed`](crate::Error::TextureCreationFailed)
+    /// if there is not enough memory in the requested [`MemoryRegion`] (or in
+    /// either region, for [`MemoryRegion::PreferVram`]).
     #[doc(alias = "C3D_TexInitWithParams")]
+    #[doc(alias = "C3D_TexInitVRAM")]
     pub fn new(params: TexParams) -> super::Result<Self> {
+        Self::validate_dimensions(params.width, params.height)?;
+
+        match params.memory {
+            MemoryRegion::Linear => Self::init(&params, false),
+            MemoryRegion::Vram => Self::init(&params, true),
+            MemoryRegion::PreferVram => {
+                Self::init(&params, true).or_else(|_| Self::init(&params, false))
+            }
+        }
+    }
+
+    /// Check whether a texture of the given size is supported by the GPU, i.e.
+    /// both dimensions are powers of two between
+    /// [`MIN_TEXTURE_SIZE`](crate::limits::MIN_TEXTURE_SIZE) and
+    /// [`MAX_TEXTURE_SIZE`](crate::limits::MAX_TEXTURE_SIZE).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidTextureDimensions`](crate::Error::InvalidTextureDimensions)
+    /// if the size is not supported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::texture::Tex;
+    /// assert!(Tex::validate_dimensions(256, 64).is_ok());
+    /// assert!(Tex::validate_dimensions(480, 320).is_err());
+    /// assert!(Tex::validate_dimensions(2048, 2048).is_err());
+    /// ```
+    pub fn validate_dimensions(width: u16, height: u16) -> super::Result<()> {
+        let valid = |size: u16| {
+            size.is_power_of_two()
+                && (crate::limits::MIN_TEXTURE_SIZE..=crate::limits::MAX_TEXTURE_SIZE)
+                    .contains(&size)
+        };
+
+        if valid(width) && valid(height) {
+            Ok(())
+        } else {
+            Err(super::Error::InvalidTextureDimensions { width, height })
+        }
+    }
+
+    fn init(params: &TexParams, on_vram: bool) -> super::Result<Self> {
         let raw = unsafe {
             let mut raw = Box::<citro3d_sys::C3D_Tex>::new_uninit();
             assert!(
@@ -216,12 +429,20 @@ impl Tex {
                 _bitfield_1: Default::default(),
                 __bindgen_padding_0: Default::default(),
             };
-            cparams.set_onVram(params.use_vram);
+            cparams.set_onVram(on_vram);
             cparams.set_format(params.format as _);
             cparams.set_type(params.kind as _);
-            cparams.set_maxLevel(0);
+            cparams.set_maxLevel(params.clamped_max_level());
             if !citro3d_sys::C3D_TexInitWithParams(raw.as_mut_ptr(), cube, cparams) {
-                return Err(super::Error::FailedToInitialize);
+                if !cube.is_null() {
+                    drop(Box::from_raw(cube));
+                }
+                return Err(super::Error::TextureCreationFailed {
+                    width: params.width,
+                    height: params.height,
+                    format: params.format,
+                    vram: on_vram,
+                });
             }
             raw.assume_init()
         };
@@ -229,37 +450,160 @@ impl Tex {
         Ok(Self(raw))
     }
 
+    /// Get the highest mipmap level of the texture, i.e. the number of levels
+    /// below the full-size image. See [`TexParams::max_level`].
+    pub fn max_level(&self) -> u8 {
+        unsafe { self.0.as_ref().__bindgen_anon_3.__bindgen_anon_1.maxLevel }
+    }
+
+    /// Get the kind of texture this is.
+    #[doc(alias = "C3D_TexGetType")]
     pub fn kind(&self) -> TexKind {
         unsafe { citro3d_sys::C3D_TexGetType(self.0.as_ptr()) }.into()
     }
 
+    /// Get the width of the texture, in pixels.
     pub fn width(&self) -> u16 {
         unsafe { self.0.as_ref().__bindgen_anon_2.__bindgen_anon_1.width }
     }
+
+    /// Get the height of the texture, in pixels.
     pub fn height(&self) -> u16 {
         unsafe { self.0.as_ref().__bindgen_anon_2.__bindgen_anon_1.height }
     }
 
+    /// Get the memory region the texture's pixel data was allocated in. This is
+    /// either [`MemoryRegion::Linear`] or [`MemoryRegion::Vram`].
+    pub fn memory_region(&self) -> MemoryRegion {
+        let data = unsafe {
+            let raw = self.0.as_ref();
+            match self.kind() {
+                TexKind::CubeMap | TexKind::ShadowCube => (*raw.__bindgen_anon_1.cube).data[0],
+                TexKind::Tex2d | TexKind::Shadow2d => raw.__bindgen_anon_1.data,
+            }
+        };
+
+        let vram = ctru_sys::OS_VRAM_VADDR..ctru_sys::OS_VRAM_VADDR + ctru_sys::OS_VRAM_SIZE;
+        if vram.contains(&(data as u32)) {
+            MemoryRegion::Vram
+        } else {
+            MemoryRegion::Linear
+        }
+    }
+
+    /// Get the format of the texture's pixel data.
     pub fn format(&self) -> TexFormat {
         TexFormat::try_from(unsafe { self.0.as_ref().fmt() }).expect("unknown texture colour type")
     }
 
+    /// Bind the texture to the given texture unit, for use by subsequent draw calls.
+    /// [`TexEnv`](crate::texenv::TexEnv) stages sample it with the unit's
+    /// [`Source`](crate::texenv::Source), e.g. [`Source::Texture1`](crate::texenv::Source::Texture1)
+    /// for [`TexUnit::Unit1`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::texture::{Tex, TexParams, TexUnit};
+    /// let base = Tex::new(TexParams::new_2d(64, 64)).unwrap();
+    /// let lightmap = Tex::new(TexParams::new_2d(32, 32)).unwrap();
+    ///
+    /// base.bind(TexUnit::Unit0);
+    /// lightmap.bind(TexUnit::Unit1);
+    /// ```
     #[doc(alias = "C3D_TexBind")]
-    pub fn bind(&self, unit_id: i32) {
-        unsafe { citro3d_sys::C3D_TexBind(unit_id, self.as_raw().cast_mut()) }
+    pub fn bind(&self, unit: TexUnit) {
+        unsafe { citro3d_sys::C3D_TexBind(unit.id(), self.as_raw().cast_mut()) }
     }
 
+    /// Upload pixel data to the texture. The data is expected to already be in
+    /// the texture's [`TexFormat`] and in the GPU's tiled layout. Compressed
+    /// [`TexFormat::Etc1`] and [`TexFormat::Etc1A4`] data is uploaded as-is,
+    /// e.g. as produced by `tex3ds`.
+    ///
+    /// For cube maps this only fills [`Face::PositiveX`]; use
+    /// [`Tex::upload_face`] for the other faces.
+    ///
+    /// # Panics
+    ///
+    /// If `data` is too small to fill the texture, or for a
+    /// [compressed](TexFormat::is_compressed) format, if it isn't exactly the
+    /// size of the compressed texture.
     #[doc(alias = "C3D_TexUpload")]
-    pub fn upload<T: AsRef<[u8]>>(&self, data: T) {
+    pub fn upload<T: AsRef<[u8]>>(&mut self, data: T) {
         let buf = data.as_ref();
+        self.check_upload_size(buf, 0);
 
-        let (width, height) = (self.width(), self.height());
-        let (width, height) = (width as usize, height as usize);
-        assert!(buf.len() >= width * height * self.format().bits_per_pixel() / 8);
+        unsafe { citro3d_sys::C3D_TexUpload(self.as_raw_mut(), buf.as_ptr().cast()) }
+    }
 
-        unsafe { citro3d_sys::C3D_TexUpload(self.as_raw().cast_mut(), buf.as_ptr().cast()) }
+    /// Upload pixel data to one face of the texture, e.g. one side of a cube
+    /// map. The data is expected to already be in the texture's [`TexFormat`]
+    /// and in the GPU's tiled layout.
+    ///
+    /// # Panics
+    ///
+    /// If `data` is too small to fill the face, or for a
+    /// [compressed](TexFormat::is_compressed) format, if it isn't exactly the
+    /// size of the compressed face.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::texture::{Face, Tex, TexParams, TexUnit};
+    /// let mut tex = Tex::new(TexParams::new_cube(32)).unwrap();
+    /// for face in [
+    ///     Face::PositiveX,
+    ///     Face::NegativeX,
+    ///     Face::PositiveY,
+    ///     Face::NegativeY,
+    ///     Face::PositiveZ,
+    ///     Face::NegativeZ,
+    /// ] {
+    ///     tex.upload_face(face, vec![0xFF; 32 * 32 * 4]);
+    /// }
+    /// tex.bind(TexUnit::Unit0);
+    /// ```
+    #[doc(alias = "C3D_TexLoadImage")]
+    pub fn upload_face<T: AsRef<[u8]>>(&mut self, face: Face, data: T) {
+        self.upload_level(face, 0, data);
     }
 
+    /// Upload pixel data to one mipmap level of a face of the texture. Level
+    /// `0` is the full-size image, and each subsequent level is half the width
+    /// and height of the previous one.
+    ///
+    /// # Panics
+    ///
+    /// If `level` is greater than the texture's [`Tex::max_level`], or if
+    /// `data` is too small to fill the level. For a [compressed](TexFormat::is_compressed)
+    /// format, `data` must be exactly the size of the compressed level.
+    #[doc(alias = "C3D_TexLoadImage")]
+    pub fn upload_level<T: AsRef<[u8]>>(&mut self, face: Face, level: u8, data: T) {
+        let buf = data.as_ref();
+
+        // citro3d doesn't check the level, and would write past the texture data
+        assert!(
+            level <= self.max_level(),
+            "mipmap level {level} out of range for texture with max level {}",
+            self.max_level()
+        );
+
+        self.check_upload_size(buf, level);
+
+        unsafe {
+            citro3d_sys::C3D_TexLoadImage(
+                self.as_raw_mut(),
+                buf.as_ptr().cast(),
+                face as _,
+                level.into(),
+            )
+        }
+    }
+
+    /// Set the filters used when the texture is magnified and minified.
     #[doc(alias = "C3D_TexSetFilter")]
     pub fn set_filter(&self, mag_filter: TextureFilterParam, min_filter: TextureFilterParam) {
         unsafe {
@@ -271,6 +615,60 @@ impl Tex {
         }
     }
 
+    /// Set the filter used to select between mipmap levels when the texture is
+    /// minified.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NoMipmapLevels`](crate::Error::NoMipmapLevels) if the
+    /// texture was created without mipmaps, see [`TexParams::max_level`].
+    #[doc(alias = "C3D_TexSetFilterMipmap")]
+    pub fn set_filter_mipmap(&mut self, filter: TextureFilterParam) -> crate::Result<()> {
+        self.check_has_mipmaps()?;
+        unsafe { citro3d_sys::C3D_TexSetFilterMipmap(self.as_raw_mut(), filter as u32) }
+        Ok(())
+    }
+
+    /// Generate the mipmap levels of the given face of the texture by
+    /// downscaling the full-size image, which must already be uploaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NoMipmapLevels`](crate::Error::NoMipmapLevels) if the
+    /// texture was created without mipmaps, see [`TexParams::max_level`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::texture::{Face, Tex, TexParams, TextureFilterParam};
+    /// let mut tex = Tex::new(TexParams::new_2d(64, 64).max_level(3)).unwrap();
+    /// tex.upload(vec![0xFF; 64 * 64 * 4]);
+    /// tex.generate_mipmap(Face::TEX_2D).unwrap();
+    ///
+    /// tex.set_filter(TextureFilterParam::Linear, TextureFilterParam::Linear);
+    /// tex.set_filter_mipmap(TextureFilterParam::Linear).unwrap();
+    ///
+    /// let mut flat = Tex::new(TexParams::new_2d(64, 64)).unwrap();
+    /// assert!(flat.generate_mipmap(Face::TEX_2D).is_err());
+    /// ```
+    #[doc(alias = "C3D_TexGenerateMipmap")]
+    pub fn generate_mipmap(&mut self, face: Face) -> crate::Result<()> {
+        self.check_has_mipmaps()?;
+        unsafe { citro3d_sys::C3D_TexGenerateMipmap(self.as_raw_mut(), face as _) }
+        Ok(())
+    }
+
+    fn check_has_mipmaps(&self) -> crate::Result<()> {
+        if self.max_level() == 0 {
+            Err(crate::Error::NoMipmapLevels)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Set how texture coordinates outside of `0.0..=1.0` are handled in the
+    /// S (horizontal) and T (vertical) directions.
     #[doc(alias = "C3D_TexSetWrap")]
     pub fn set_wrap(&self, wrap_s: TextureWrapParam, wrap_t: TextureWrapParam) {
         unsafe {
@@ -278,6 +676,48 @@ impl Tex {
         }
     }
 
+    /// Set the color sampled outside of the texture when using
+    /// [`TextureWrapParam::ClampToBorder`].
+    ///
+    /// The color is packed as `0xAABBGGRR`, like the other colors used by the
+    /// GPU (e.g. [`TexEnv::color`](crate::texenv::TexEnv::color)).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::texture::{Tex, TexParams, TextureWrapParam};
+    /// let mut tex = Tex::new(TexParams::new_2d(64, 64)).unwrap();
+    /// tex.set_wrap(TextureWrapParam::ClampToBorder, TextureWrapParam::ClampToBorder);
+    /// // Transparent outside the decal
+    /// tex.set_border_color(0x00_FF_FF_FF);
+    /// ```
+    pub fn set_border_color(&mut self, color: u32) {
+        unsafe { (*self.as_raw_mut()).border = color }
+    }
+
+    /// Check that `data` can fill one face of the given mipmap level. Compressed
+    /// data must match the size of the level exactly, since data of another size
+    /// was compressed for a different texture and would be decoded as garbage.
+    fn check_upload_size(&self, data: &[u8], level: u8) {
+        let (width, height) = (self.width() as usize, self.height() as usize);
+        let (width, height) = (width >> level, height >> level);
+        let size = width * height * self.format().bits_per_pixel() / 8;
+
+        if self.format().is_compressed() {
+            assert_eq!(
+                data.len(),
+                size,
+                "compressed texture data must be exactly the size of the level"
+            );
+        } else {
+            assert!(
+                data.len() >= size,
+                "texture data is too small to fill the level"
+            );
+        }
+    }
+
     pub fn as_raw(&self) -> *const citro3d_sys::C3D_Tex {
         self.0.as_ptr() as *const _
     }
@@ -290,6 +730,53 @@ impl Tex {
 impl Drop for Tex {
     #[doc(alias = "C3D_TexDelete")]
     fn drop(&mut self) {
-        unsafe { citro3d_sys::C3D_TexDelete(self.as_raw().cast_mut()) }
+        unsafe {
+            let cube = match self.kind() {
+                TexKind::CubeMap | TexKind::ShadowCube => self.0.as_ref().__bindgen_anon_1.cube,
+                TexKind::Tex2d | TexKind::Shadow2d => std::ptr::null_mut(),
+            };
+            citro3d_sys::C3D_TexDelete(self.as_raw_mut());
+            // The face pointers of a cube map were allocated by `Tex::new`
+            if !cube.is_null() {
+                drop(Box::from_raw(cube));
+            }
+            // The C3D_Tex itself was allocated by `Tex::new`
+            drop(Box::from_raw(self.as_raw_mut()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upload_etc1() {
+        for format in [TexFormat::Etc1, TexFormat::Etc1A4] {
+            let mut tex = Tex::new(TexParams::new_2d(8, 8).format(format)).unwrap();
+            assert_eq!(tex.format(), format);
+            assert!(tex.format().is_compressed());
+
+            // Four 4x4 blocks
+            let block_size = if format == TexFormat::Etc1A4 { 16 } else { 8 };
+            tex.upload(vec![0x5A; 4 * block_size]);
+            tex.bind(TexUnit::Unit0);
+        }
+    }
+
+    #[test]
+    #[should_panic = "exactly the size of the level"]
+    fn upload_etc1_wrong_size() {
+        let mut tex = Tex::new(TexParams::new_2d(8, 8).format(TexFormat::Etc1)).unwrap();
+        // One block more than the four 4x4 blocks of the texture
+        tex.upload(vec![0x5A; 5 * 8]);
+    }
+
+    #[test]
+    #[should_panic = "out of range"]
+    fn upload_level_out_of_range() {
+        let mut tex = Tex::new(TexParams::new_2d(8, 8)).unwrap();
+        assert_eq!(tex.max_level(), 0);
+        tex.upload_level(Face::TEX_2D, 1, vec![0; 4 * 4 * 4]);
     }
 }
@@ -3,8 +3,8 @@
 
 use std::ops::Range;
 
-use crate::math::{FVec4, IVec, Matrix4};
-use crate::{shader, Instance};
+use crate::math::{FVec4, IVec, Matrix2, Matrix3, Matrix4};
+use crate::{shader, Error, Instance};
 
 /// The index of a uniform within a [`shader::Program`].
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -32,10 +32,20 @@ pub enum Uniform {
     Float3([FVec4; 3]),
     /// Matrix/4 element float uniform (`.fvec name[4]`)
     Float4(Matrix4),
+    /// 3x3 matrix uniform (`.fvec name[3]`)
+    Matrix3(Matrix3),
+    /// 2x2 matrix uniform (`.fvec name[2]`)
+    Matrix2(Matrix2),
     /// Bool uniform (`.bool name`)
     Bool(bool),
     /// Integer uniform (`.ivec name`)
     Int(IVec),
+    /// Two element integer uniform (`.ivec name[2]`)
+    Int2([IVec; 2]),
+    /// Three element integer uniform (`.ivec name[3]`)
+    Int3([IVec; 3]),
+    /// Four element integer uniform (`.ivec name[4]`), i.e. all integer registers
+    Int4([IVec; 4]),
 }
 impl Uniform {
     /// Get range of valid indexes for this uniform to bind to
@@ -43,10 +53,15 @@ impl Uniform {
         // these indexes are from the uniform table in the shader see: https://www.3dbrew.org/wiki/SHBIN#Uniform_Table_Entry
         // the input registers then are excluded by libctru, see: https://github.com/devkitPro/libctru/blob/0da8705527f03b4b08ff7fee4dd1b7f28df37905/libctru/source/gpu/shbin.c#L93
         match self {
-            Uniform::Float(_) | Uniform::Float2(_) | Uniform::Float3(_) | Uniform::Float4(_) => {
-                Index(0)..Index(0x60)
+            Uniform::Float(_)
+            | Uniform::Float2(_)
+            | Uniform::Float3(_)
+            | Uniform::Float4(_)
+            | Uniform::Matrix3(_)
+            | Uniform::Matrix2(_) => FLOAT_RANGE,
+            Uniform::Int(_) | Uniform::Int2(_) | Uniform::Int3(_) | Uniform::Int4(_) => {
+                Index(0x60)..Index(0x64)
             }
-            Uniform::Int(_) => Index(0x60)..Index(0x64),
             // this gap is intentional
             Uniform::Bool(_) => Index(0x68)..Index(0x79),
         }
@@ -56,36 +71,47 @@ impl Uniform {
     pub fn len(&self) -> usize {
         match self {
             Uniform::Float(_) => 1,
-            Uniform::Float2(_) => 2,
-            Uniform::Float3(_) => 3,
-            Uniform::Float4(_) => 4,
+            Uniform::Float2(_) | Uniform::Matrix2(_) | Uniform::Int2(_) => 2,
+            Uniform::Float3(_) | Uniform::Matrix3(_) | Uniform::Int3(_) => 3,
+            Uniform::Float4(_) | Uniform::Int4(_) => 4,
             Uniform::Bool(_) | Uniform::Int(_) => 1,
         }
     }
 
+    /// Check whether this uniform can be bound at the given `index`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidUniformIndex`] if `index` is not in [`Self::index_range`]
+    /// * [`Error::UniformOverflow`] if the uniform would not fit in the remaining
+    ///   registers starting at `index`
+    pub fn check_index(&self, index: Index) -> crate::Result<()> {
+        check_range(self.index_range(), index, self.len())
+    }
+
     /// Bind a uniform
     ///
     /// Note: `_instance` is here to ensure unique access to the global uniform buffers
     /// otherwise we could race and/or violate aliasing
-    pub(crate) fn bind(self, _instance: &mut Instance, ty: shader::Type, index: Index) {
-        assert!(
-            self.index_range().contains(&index),
-            "tried to bind uniform to an invalid index (index: {}, valid range: {}..{})",
-            index.0,
-            self.index_range().start.0,
-            self.index_range().end.0
-        );
-        assert!(self.index_range().end.0 as usize >= self.len() + index.0 as usize, "tried to bind a uniform that would overflow the uniform buffer. index was {}, size was {} max is {}", index.0, self.len(), self.index_range().end.0);
-        let set_fvs = |fs: &[FVec4]| {
-            for (off, f) in fs.iter().enumerate() {
+    pub(crate) fn bind(
+        self,
+        _instance: &mut Instance,
+        ty: shader::Type,
+        index: Index,
+    ) -> crate::Result<()> {
+        self.check_index(index)?;
+
+        let set_fvs = |fs: &[FVec4]| set_float_uniforms(ty, index, fs);
+        let set_ivs = |is: &[IVec]| {
+            for (off, i) in is.iter().enumerate() {
                 unsafe {
-                    citro3d_sys::C3D_FVUnifSet(
+                    citro3d_sys::C3D_IVUnifSet(
                         ty.into(),
                         (index.0 as usize + off) as i32,
-                        f.x(),
-                        f.y(),
-                        f.z(),
-                        f.w(),
+                        i.x() as i32,
+                        i.y() as i32,
+                        i.z() as i32,
+                        i.w() as i32,
                     );
                 }
             }
@@ -94,16 +120,10 @@ impl Uniform {
             Uniform::Bool(b) => unsafe {
                 citro3d_sys::C3D_BoolUnifSet(ty.into(), index.into(), b);
             },
-            Uniform::Int(i) => unsafe {
-                citro3d_sys::C3D_IVUnifSet(
-                    ty.into(),
-                    index.into(),
-                    i.x() as i32,
-                    i.y() as i32,
-                    i.z() as i32,
-                    i.w() as i32,
-                );
-            },
+            Uniform::Int(i) => set_ivs(&[i]),
+            Uniform::Int2(is) => set_ivs(&is),
+            Uniform::Int3(is) => set_ivs(&is),
+            Uniform::Int4(is) => set_ivs(&is),
             Uniform::Float(f) => set_fvs(&[f]),
             Uniform::Float2(fs) => {
                 set_fvs(&fs);
@@ -112,6 +132,81 @@ impl Uniform {
             Uniform::Float4(m) => {
                 set_fvs(&m.rows_wzyx());
             }
+            Uniform::Matrix3(m) => set_fvs(&m.rows_wzyx()),
+            Uniform::Matrix2(m) => set_fvs(&m.rows_wzyx()),
+        }
+        Ok(())
+    }
+}
+
+/// The range of float registers, which [`bind_float_array`] can write to.
+pub(crate) const FLOAT_RANGE: Range<Index> = Index(0)..Index(0x60);
+
+/// Bind an arbitrary number of consecutive float vector uniforms, e.g. for a
+/// `.fvec name[N]` array in the shader.
+///
+/// Like [`Uniform::bind`], `_instance` ensures unique access to the global
+/// uniform buffers.
+pub(crate) fn bind_float_array(
+    _instance: &mut Instance,
+    ty: shader::Type,
+    index: Index,
+    values: &[FVec4],
+) -> crate::Result<()> {
+    check_range(FLOAT_RANGE, index, values.len())?;
+    set_float_uniforms(ty, index, values);
+    Ok(())
+}
+
+/// Get a writable view of `count` consecutive float vector uniforms, starting
+/// at `index`. The registers are marked dirty, so whatever is written to the
+/// returned slice is uploaded with the next draw call.
+///
+/// The slice borrows `_instance`, which prevents any other access to the
+/// global uniform buffers while it is alive.
+pub(crate) fn float_array_mut(
+    _instance: &mut Instance,
+    ty: shader::Type,
+    index: Index,
+    count: usize,
+) -> crate::Result<&mut [FVec4]> {
+    check_range(FLOAT_RANGE, index, count)?;
+    unsafe {
+        let ptr = citro3d_sys::C3D_FVUnifWritePtr(ty.into(), index.0.into(), count as i32);
+        // Safety: FVec4 is repr(transparent) over C3D_FVec, and the range was
+        // checked to lie within the uniform buffer of this shader type
+        Ok(std::slice::from_raw_parts_mut(ptr.cast::<FVec4>(), count))
+    }
+}
+
+fn check_range(range: Range<Index>, index: Index, len: usize) -> crate::Result<()> {
+    if !range.contains(&index) {
+        return Err(Error::InvalidUniformIndex {
+            index: index.0,
+            valid: range.start.0..range.end.0,
+        });
+    }
+    if index.0 as usize + len > range.end.0 as usize {
+        return Err(Error::UniformOverflow {
+            index: index.0,
+            len,
+            end: range.end.0,
+        });
+    }
+    Ok(())
+}
+
+fn set_float_uniforms(ty: shader::Type, index: Index, values: &[FVec4]) {
+    for (off, f) in values.iter().enumerate() {
+        unsafe {
+            citro3d_sys::C3D_FVUnifSet(
+                ty.into(),
+                (index.0 as usize + off) as i32,
+                f.x(),
+                f.y(),
+                f.z(),
+                f.w(),
+            );
         }
     }
 }
@@ -121,6 +216,16 @@ impl From<Matrix4> for Uniform {
         Self::Float4(value)
     }
 }
+impl From<Matrix3> for Uniform {
+    fn from(value: Matrix3) -> Self {
+        Self::Matrix3(value)
+    }
+}
+impl From<Matrix2> for Uniform {
+    fn from(value: Matrix2) -> Self {
+        Self::Matrix2(value)
+    }
+}
 impl From<[FVec4; 3]> for Uniform {
     fn from(value: [FVec4; 3]) -> Self {
         Self::Float3(value)
@@ -137,11 +242,46 @@ impl From<FVec4> for Uniform {
         Self::Float(value)
     }
 }
+impl From<[f32; 4]> for Uniform {
+    fn from(value: [f32; 4]) -> Self {
+        Self::Float(value.into())
+    }
+}
+impl From<(f32, f32, f32, f32)> for Uniform {
+    fn from(value: (f32, f32, f32, f32)) -> Self {
+        Self::Float(value.into())
+    }
+}
 impl From<IVec> for Uniform {
     fn from(value: IVec) -> Self {
         Self::Int(value)
     }
 }
+impl From<[IVec; 2]> for Uniform {
+    fn from(value: [IVec; 2]) -> Self {
+        Self::Int2(value)
+    }
+}
+impl From<[IVec; 3]> for Uniform {
+    fn from(value: [IVec; 3]) -> Self {
+        Self::Int3(value)
+    }
+}
+impl From<[IVec; 4]> for Uniform {
+    fn from(value: [IVec; 4]) -> Self {
+        Self::Int4(value)
+    }
+}
+impl From<[u8; 4]> for Uniform {
+    fn from(value: [u8; 4]) -> Self {
+        Self::Int(value.into())
+    }
+}
+impl From<(u8, u8, u8, u8)> for Uniform {
+    fn from(value: (u8, u8, u8, u8)) -> Self {
+        Self::Int(value.into())
+    }
+}
 impl From<bool> for Uniform {
     fn from(value: bool) -> Self {
         Self::Bool(value)
@@ -166,3 +306,65 @@ impl From<glam::Mat4> for Uniform {
         Self::Float4(value.into())
     }
 }
+
+#[cfg(feature = "glam")]
+impl From<glam::Mat3> for Uniform {
+    fn from(value: glam::Mat3) -> Self {
+        Self::Matrix3(value.into())
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::Quat> for Uniform {
+    fn from(value: glam::Quat) -> Self {
+        Self::Float4(value.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_index() {
+        let mtx = Uniform::from(Matrix4::identity());
+        assert!(mtx.check_index(Index(0)).is_ok());
+        assert!(mtx.check_index(Index(0x5C)).is_ok());
+        assert!(matches!(
+            mtx.check_index(Index(0x5D)),
+            Err(Error::UniformOverflow {
+                index: 0x5D,
+                len: 4,
+                end: 0x60
+            })
+        ));
+        assert!(matches!(
+            mtx.check_index(Index(0x60)),
+            Err(Error::InvalidUniformIndex { index: 0x60, .. })
+        ));
+
+        let b = Uniform::from(true);
+        assert!(b.check_index(Index(0x68)).is_ok());
+        assert!(matches!(
+            b.check_index(Index(0x64)),
+            Err(Error::InvalidUniformIndex { index: 0x64, .. })
+        ));
+    }
+
+    #[test]
+    fn check_array_range() {
+        assert!(check_range(FLOAT_RANGE, Index(0x50), 16).is_ok());
+        assert!(matches!(
+            check_range(FLOAT_RANGE, Index(0x51), 16),
+            Err(Error::UniformOverflow {
+                index: 0x51,
+                len: 16,
+                end: 0x60
+            })
+        ));
+        assert!(matches!(
+            check_range(FLOAT_RANGE, Index(0x60), 1),
+            Err(Error::InvalidUniformIndex { index: 0x60, .. })
+        ));
+    }
+}
@@ -4,3 +4,56 @@ pub fn is_linear_ptr<P>(p: *const P) -> bool {
     addr >= ctru_sys::OS_FCRAM_VADDR as usize
         && addr < (ctru_sys::OS_FCRAM_VADDR as usize + ctru_sys::OS_FCRAM_SIZE as usize)
 }
+
+/// Flush the CPU data cache for the given memory, so that CPU writes to it are
+/// visible to the GPU.
+pub fn flush_data_cache<T>(data: &[T]) -> crate::Result<()> {
+    let size = std::mem::size_of_val(data).try_into()?;
+    let res = unsafe { ctru_sys::GSPGPU_FlushDataCache(data.as_ptr().cast(), size) };
+    if res == 0 {
+        Ok(())
+    } else {
+        Err(crate::Error::System(res))
+    }
+}
+
+/// Convert a float to the 24-bit float format used by the PICA registers
+/// (1 sign bit, 7 exponent bits, 16 mantissa bits), like libctru's `f32tof24`.
+pub fn f32_to_f24(f: f32) -> u32 {
+    let bits = f.to_bits();
+    let sign = bits >> 31;
+    let exponent = ((bits >> 23) & 0xFF) as i32 - 127 + 63;
+    let mantissa = (bits & 0x7F_FFFF) >> 7;
+
+    if exponent < 0 {
+        sign << 23
+    } else if exponent > 0x7F {
+        (sign << 23) | (0x7F << 16)
+    } else {
+        (sign << 23) | ((exponent as u32) << 16) | mantissa
+    }
+}
+
+/// Write `values` to consecutive GPU registers starting at `reg`, through the
+/// command buffer citro3d is currently using.
+pub fn write_gpu_regs(reg: u32, values: &[u32]) {
+    // GPUCMD_HEADER(incremental, mask, reg), writing all bytes of each register
+    let header = (u32::from(values.len() > 1) << 31) | (0xF << 16) | reg;
+    unsafe {
+        ctru_sys::GPUCMD_Add(header, values.as_ptr(), values.len() as u32);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn f24_conversion() {
+        assert_eq!(f32_to_f24(0.0), 0);
+        assert_eq!(f32_to_f24(1.0), 0x3F_0000);
+        assert_eq!(f32_to_f24(-2.0), 0xC0_0000);
+        assert_eq!(f32_to_f24(0.5), 0x3E_0000);
+        assert_eq!(f32_to_f24(f32::MAX), 0x7F_0000);
+    }
+}